@@ -1,5 +1,5 @@
 use heapless::Vec as HeaplessVec;
-advent_of_code::solution!(2);
+advent_of_code::solution!(2024, 2);
 
 const MAX_LEVELS: usize = 20;
 
@@ -45,13 +45,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(4));
     }
 }
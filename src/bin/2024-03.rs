@@ -1,4 +1,4 @@
-advent_of_code::solution!(3);
+advent_of_code::solution!(2024, 3);
 
 use regex::Regex;
 
@@ -42,19 +42,8 @@ pub fn part_two(input: &str) -> Option<u32> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(161));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(48));
+    advent_of_code::examples! {
+        1 => (Some(161), None),
+        2 => (None, Some(48)),
     }
 }
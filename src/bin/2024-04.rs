@@ -4,7 +4,7 @@ use mygrid::{
 };
 use rayon::prelude::*;
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(2024, 4);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let grid = Grid::new_from_str(input, |c| c);
@@ -57,13 +57,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(18));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(9));
     }
 }
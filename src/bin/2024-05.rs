@@ -1,8 +1,9 @@
+use advent_of_code::collections::{Bounded, TryCollect};
 use heapless::FnvIndexMap as HeaplessMap;
 use heapless::FnvIndexSet as HeaplessSet;
 use heapless::Vec as HeaplessVec;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(2024, 5, parse = parse_input);
 
 type PageNumBag = HeaplessSet<u8, 128>;
 type OrderRules = HeaplessMap<u8, PageNumBag, 128>;
@@ -16,16 +17,20 @@ fn parse_input(input: &str) -> (OrderRules, Vec<PagesToPrint>) {
         let left: u8 = left.parse().unwrap();
         let right: u8 = right.parse().unwrap();
         if let Some(set) = map.get_mut(&left) {
-            set.insert(right).unwrap();
+            set.try_insert(right).unwrap();
         } else {
             let mut set = HeaplessSet::new();
-            set.insert(right).unwrap();
-            map.insert(left, set).unwrap();
+            set.try_insert(right).unwrap();
+            map.try_insert((left, set)).unwrap();
         }
     }
     let mut pages_vec: Vec<PagesToPrint> = Vec::new();
     for line in pages.lines().filter(|l| !l.is_empty()) {
-        let nums: PagesToPrint = line.split(',').map(|s| s.parse().unwrap()).collect();
+        let nums: PagesToPrint = line
+            .split(',')
+            .map(|s| s.parse().unwrap())
+            .try_collect()
+            .unwrap();
         pages_vec.push(nums);
     }
     (map, pages_vec)
@@ -40,7 +45,7 @@ fn middle_num(page: &PagesToPrint) -> u8 {
 fn is_correctly_ordered(page: &PagesToPrint, rules: &OrderRules) -> bool {
     let mut nums_before: PageNumBag = PageNumBag::new();
     for num in page {
-        nums_before.insert(*num).unwrap();
+        nums_before.try_insert(*num).unwrap();
         let is_num_valid = match rules.get(num) {
             None => true,
             Some(check_before) => check_before.intersection(&nums_before).count() == 0,
@@ -79,24 +84,23 @@ fn order_pages_in_place(pages: &mut PagesToPrint, rules: &OrderRules) {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (rules, pages) = parse_input(input);
+pub fn part_one((rules, pages): &(OrderRules, Vec<PagesToPrint>)) -> Option<u32> {
     let sum = pages
         .iter()
-        .filter(|p| is_correctly_ordered(p, &rules))
+        .filter(|p| is_correctly_ordered(p, rules))
         .map(middle_num)
         .map(|n| n as u32)
         .sum();
     Some(sum)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (rules, mut pages) = parse_input(input);
+pub fn part_two((rules, pages): &(OrderRules, Vec<PagesToPrint>)) -> Option<u32> {
+    let mut pages = pages.clone();
     let sum = pages
         .iter_mut()
-        .filter(|p| !is_correctly_ordered(p, &rules))
+        .filter(|p| !is_correctly_ordered(p, rules))
         .map(|p| {
-            order_pages_in_place(p, &rules);
+            order_pages_in_place(p, rules);
             middle_num(p) as u32
         })
         .sum();
@@ -107,15 +111,8 @@ pub fn part_two(input: &str) -> Option<u32> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(143));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(123));
+    advent_of_code::examples! {
+        parse = parse_input,
+        _ => (Some(143), Some(123)),
     }
 }
@@ -1,4 +1,5 @@
-advent_of_code::solution!(6);
+advent_of_code::solution!(2024, 6, generate = generate, reference = reference);
+use advent_of_code::template::generate::Rng;
 use mygrid::direction::{Direction, UP};
 use mygrid::grid::Grid;
 use mygrid::point::Point;
@@ -56,6 +57,7 @@ fn get_guard_path_positions_assuming_no_loops(
     let mut visited = FxHashSet::with_capacity_and_hasher(10_000, Default::default());
     let mut jump_table = JumpTable::with_capacity_and_hasher(10_000, Default::default());
     let mut old_guard = guard.clone();
+    visited.insert(start_pos);
 
     while let Some(new_guard) = guard.turn(grid) {
         if new_guard.direction != guard.direction {
@@ -80,6 +82,8 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     let count = visited
         .par_iter()
+        // the guard would notice an obstacle placed on its starting position.
+        .filter(|&&pos| pos != start_pos)
         .filter(|&&pos| {
             let mut grid = grid.clone();
             grid[pos] = 'O';
@@ -115,19 +119,121 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(count as u32)
 }
 
+/// Naive solution: walks the guard cell by cell and tries an obstacle on every free cell.
+mod reference {
+    use std::collections::HashSet;
+
+    type Position = (isize, isize);
+
+    const DIRECTIONS: [Position; 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+    fn parse(input: &str) -> (Vec<Vec<u8>>, Position) {
+        let grid: Vec<Vec<u8>> = input.lines().map(|l| l.bytes().collect()).collect();
+        let start = grid
+            .iter()
+            .enumerate()
+            .find_map(|(r, row)| {
+                let c = row.iter().position(|&b| b == b'^')?;
+                Some((r as isize, c as isize))
+            })
+            .unwrap();
+        (grid, start)
+    }
+
+    fn get(grid: &[Vec<u8>], (r, c): Position) -> Option<u8> {
+        let row = grid.get(usize::try_from(r).ok()?)?;
+        row.get(usize::try_from(c).ok()?).copied()
+    }
+
+    /// Returns the positions the guard visits before leaving the grid, or `None` if it walks in a loop.
+    pub fn walk(grid: &[Vec<u8>], start: Position) -> Option<HashSet<Position>> {
+        let mut seen = HashSet::new();
+        let (mut position, mut direction) = (start, 0);
+
+        while seen.insert((position, direction)) {
+            let (dr, dc) = DIRECTIONS[direction];
+            let next = (position.0 + dr, position.1 + dc);
+            match get(grid, next) {
+                None => return Some(seen.into_iter().map(|(p, _)| p).collect()),
+                Some(b'#') => direction = (direction + 1) % 4,
+                Some(_) => position = next,
+            }
+        }
+        None
+    }
+
+    pub fn part_one(input: &str) -> Option<u32> {
+        let (grid, start) = parse(input);
+        walk(&grid, start).map(|visited| visited.len() as u32)
+    }
+
+    pub fn part_two(input: &str) -> Option<u32> {
+        let (mut grid, start) = parse(input);
+        walk(&grid, start)?;
+
+        let mut count = 0;
+        for r in 0..grid.len() {
+            for c in 0..grid[r].len() {
+                if grid[r][c] != b'.' {
+                    continue;
+                }
+                grid[r][c] = b'#';
+                if walk(&grid, start).is_none() {
+                    count += 1;
+                }
+                grid[r][c] = b'.';
+            }
+        }
+        Some(count)
+    }
+}
+
+/// Generates a square grid of `size + 3` cells per side with a few obstacles, from which the guard can leave.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let side = size + 3;
+    loop {
+        let mut cells: Vec<u8> = (0..side * side)
+            .map(|_| if rng.chance(1, 8) { b'#' } else { b'.' })
+            .collect();
+        let start = rng.index(cells.len());
+        cells[start] = b'^';
+
+        let input: String = cells
+            .chunks(side)
+            .map(|row| format!("{}\n", String::from_utf8_lossy(row)))
+            .collect();
+
+        if reference::part_one(&input).is_some() {
+            return input;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(41));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6));
     }
+
+    // the guard never walks back over its starting position, which still counts as visited.
+    #[test]
+    fn test_start_not_revisited() {
+        let input = advent_of_code::template::read_file_part("examples", PUZZLE, 2);
+        assert_eq!(part_one(&input), Some(5));
+        assert_eq!(part_two(&input), Some(0));
+        assert_eq!(reference::part_one(&input), Some(5));
+        assert_eq!(reference::part_two(&input), Some(0));
+    }
+
+    advent_of_code::differential!(generate = generate);
 }
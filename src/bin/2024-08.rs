@@ -1,11 +1,10 @@
-use advent_of_code::into_group_map_heapless;
+use advent_of_code::collections::{group_map, tuple_combinations, Bounded};
 use heapless::FnvIndexMap as HeaplessHashMap;
 use heapless::FnvIndexSet as HeaplessHashSet;
 use heapless::Vec as HeaplessVec;
-use itertools::Itertools;
 use mygrid::grid::Grid;
 use mygrid::point::Point;
-advent_of_code::solution!(8);
+advent_of_code::solution!(2024, 8);
 
 const MAX_ANTENNA_TYPES: usize = 64;
 const MAX_ANTENNA_PER_TYPE: usize = 16;
@@ -18,7 +17,7 @@ fn solve(
 ) -> Option<u32> {
     let grid = Grid::new_char_grid_from_str(input);
 
-    let antennas: AntennaMap = into_group_map_heapless(
+    let antennas: AntennaMap = group_map(
         grid.iter_item_and_position()
             .filter(|(_, &c)| c != '.')
             .map(|(point, c)| (c, point)),
@@ -27,7 +26,7 @@ fn solve(
 
     let mut pos_set: HeaplessHashSet<Point, 2048> = HeaplessHashSet::new();
     for (_, vec) in antennas.iter() {
-        for (a, b) in vec.iter().tuple_combinations() {
+        for (a, b) in tuple_combinations::<_, MAX_ANTENNA_PER_TYPE>(vec).unwrap() {
             for (&a, &b) in [(a, b), (b, a)] {
                 update_set(&grid, &mut pos_set, (a, b));
             }
@@ -42,7 +41,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         let dir = b.as_vector_direction(&a);
         let p = a + dir;
         if grid.is_in_bounds(p) {
-            pos_set.insert(p).unwrap();
+            pos_set.try_insert(p).unwrap();
         }
     })
 }
@@ -51,10 +50,10 @@ pub fn part_two(input: &str) -> Option<u32> {
     solve(input, &mut |grid, pos_set, (a, b)| {
         let dir = b.as_vector_direction(&a);
         let mut p = a + dir;
-        pos_set.insert(a).unwrap();
-        pos_set.insert(b).unwrap();
+        pos_set.try_insert(a).unwrap();
+        pos_set.try_insert(b).unwrap();
         while grid.is_in_bounds(p) {
-            pos_set.insert(p).unwrap();
+            pos_set.try_insert(p).unwrap();
             p = p + dir;
         }
     })
@@ -66,13 +65,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(14));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(34));
     }
 }
@@ -1,6 +1,8 @@
 use std::fmt::Debug;
 
-advent_of_code::solution!(9);
+use advent_of_code::template::generate::Rng;
+
+advent_of_code::solution!(2024, 9, generate = generate, reference = reference);
 
 #[derive(Debug, Clone)]
 struct Block {
@@ -190,32 +192,78 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(checksum)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Naive solution: expands the disk map into one entry per block and moves blocks one at a time.
+mod reference {
+    fn expand(input: &str) -> Vec<Option<u64>> {
+        input
+            .trim()
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .enumerate()
+            .flat_map(|(i, len)| {
+                let file_id = (i % 2 == 0).then_some(i as u64 / 2);
+                std::iter::repeat_n(file_id, len as usize)
+            })
+            .collect()
+    }
 
-    #[test]
-    fn test_part_one_1() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(60));
+    fn checksum(disk: &[Option<u64>]) -> u64 {
+        (0..)
+            .zip(disk)
+            .filter_map(|(i, file_id)| Some(i * (*file_id)?))
+            .sum()
     }
 
-    #[test]
-    fn test_part_one_2() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(1928));
+    pub fn part_one(input: &str) -> Option<u64> {
+        let mut disk = expand(input);
+        while let (Some(free), Some(last)) = (
+            disk.iter().position(Option::is_none),
+            disk.iter().rposition(Option::is_some),
+        ) {
+            if free > last {
+                break;
+            }
+            disk.swap(free, last);
+        }
+        Some(checksum(&disk))
     }
 
-    #[test]
-    fn test_part_two_2() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(2858));
+    pub fn part_two(input: &str) -> Option<u64> {
+        let mut disk = expand(input);
+        let max_id = disk.iter().flatten().max().copied()?;
+
+        for id in (0..=max_id).rev() {
+            let start = disk.iter().position(|&b| b == Some(id))?;
+            let len = disk[start..].iter().take_while(|&&b| b == Some(id)).count();
+
+            let span = (0..start).find(|&i| disk[i..i + len].iter().all(Option::is_none));
+            if let Some(free) = span {
+                for i in 0..len {
+                    disk.swap(free + i, start + i);
+                }
+            }
+        }
+        Some(checksum(&disk))
+    }
+}
+
+/// Generates a disk map of `size` files, each followed by free space except the last one.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut map: String = (0..size.max(1))
+        .flat_map(|_| [rng.range(1, 10), rng.range(0, 10)])
+        .map(|digit| char::from(b'0' + digit as u8))
+        .collect();
+    map.pop();
+    map + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    advent_of_code::examples! {
+        1 => (Some(60), None),
+        2 => (Some(1928), Some(2858)),
     }
 
     #[test]
@@ -224,4 +272,6 @@ mod tests {
         let result = part_two(&input);
         assert_eq!(result, Some(638));
     }
+
+    advent_of_code::differential!(generate = generate);
 }
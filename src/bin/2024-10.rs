@@ -1,3 +1,4 @@
+use advent_of_code::collections::Bounded;
 use heapless::FnvIndexSet;
 use heapless::Vec as HeaplessVec;
 use mygrid::heapless_grid::HeaplessGrid;
@@ -6,7 +7,7 @@ use mygrid::{direction::ORTHOGONAL, point::Point};
 type Queue = HeaplessVec<Point, 128>;
 type Found = FnvIndexSet<Point, 32>;
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(2024, 10);
 
 #[inline]
 fn solve<const PART: u8>(input: &str) -> Option<u32> {
@@ -24,12 +25,12 @@ fn solve<const PART: u8>(input: &str) -> Option<u32> {
             let mut found_unique = Found::new();
 
             let mut q = Queue::new();
-            q.push(starting_point).unwrap();
+            q.try_insert(starting_point).unwrap();
 
             while let Some(current) = q.pop() {
                 if grid[current] == 9 {
                     found += 1;
-                    found_unique.insert(current).unwrap();
+                    found_unique.try_insert(current).unwrap();
                     continue;
                 }
                 for n in ORTHOGONAL
@@ -38,7 +39,7 @@ fn solve<const PART: u8>(input: &str) -> Option<u32> {
                     .filter(|&p| grid.is_in_bounds(p))
                     .filter(|&p| grid[p] == grid[current] + 1)
                 {
-                    q.push(n).unwrap();
+                    q.try_insert(n).unwrap();
                 }
             }
 
@@ -97,13 +98,13 @@ mod tests {
 
     #[test]
     fn test_part_one_5() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(36));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(81));
     }
 }
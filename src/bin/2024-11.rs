@@ -1,6 +1,7 @@
+use advent_of_code::template::generate::Rng;
 use rustc_hash::FxHashMap;
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(2024, 11, generate = generate);
 
 type Stone = u64;
 type Step = u8;
@@ -109,19 +110,37 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(solve(input, 75))
 }
 
+/// Generates `size` stones with up to 7 digits, like the puzzle inputs.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let stones: Vec<String> = (0..size.max(1))
+        .map(|_| {
+            let digits = rng.range(1, 8) as u32;
+            rng.range(0, 10_u64.pow(digits)).to_string()
+        })
+        .collect();
+    stones.join(" ") + "\n"
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(55312));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(65601038650482));
     }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(5), 8);
+        assert_eq!(input.split_whitespace().count(), 8);
+        assert_eq!(part_one(&input), part_one(&input.replace(' ', "\n")));
+    }
 }
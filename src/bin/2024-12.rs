@@ -3,7 +3,7 @@ use std::collections::VecDeque;
 
 use mygrid::{direction::ORTHOGONAL, grid::Grid};
 
-advent_of_code::solution!(12);
+advent_of_code::solution!(2024, 12);
 
 pub fn part_one(input: &str) -> Option<u32> {
     let grid = Grid::new_char_grid_from_str(input);
@@ -185,6 +185,10 @@ pub fn part_two(input: &str) -> Option<u32> {
 mod tests {
     use super::*;
 
+    advent_of_code::examples! {
+        _ => (Some(1930), Some(1206)),
+    }
+
     #[test]
     fn test_part_one_1() {
         let input = "AAAA\nBBCD\nBBCC\nEEEC\n";
@@ -193,19 +197,6 @@ mod tests {
         assert_eq!(result, Some(10 * 4 + 10 * 4 + 8 * 4 + 8 * 3 + 4 * 1));
     }
 
-    #[test]
-    fn test_part_one_2() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        let result = part_one(&input);
-        assert_eq!(result, Some(1930));
-    }
-
-    #[test]
-    fn test_part_two_1() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1206));
-    }
-
     #[test]
     fn test_part_two_2() {
         let input = "AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA\n";
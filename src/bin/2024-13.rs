@@ -1,6 +1,7 @@
+use advent_of_code::template::generate::Rng;
 use itertools::Itertools;
 
-advent_of_code::solution!(13);
+advent_of_code::solution!(2024, 13, generate = generate);
 
 #[derive(Debug, Clone, Copy)]
 struct Claw {
@@ -86,19 +87,45 @@ pub fn part_two(input: &str) -> Option<i128> {
     Some(cost)
 }
 
+/// Generates `size` claw machines. Half of the prizes can be reached with at most 100 presses of each button.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let a = [rng.range(10, 100), rng.range(10, 100)];
+            let b = [rng.range(10, 100), rng.range(10, 100)];
+            let prize = if rng.chance(1, 2) {
+                let (a_presses, b_presses) = (rng.range(0, 101), rng.range(0, 101));
+                [0, 1].map(|i| a[i] * a_presses + b[i] * b_presses)
+            } else {
+                [rng.range(1000, 20000), rng.range(1000, 20000)]
+            };
+            format!(
+                "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+                a[0], a[1], b[0], b[1], prize[0], prize[1]
+            )
+        })
+        .join("\n")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(480));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(459236326669));
     }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 7);
+        assert_eq!(parse_input(&input).len(), 7);
+    }
 }
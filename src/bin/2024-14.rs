@@ -1,6 +1,14 @@
 use mygrid::{direction::Direction, heapless_grid::HeaplessGrid, point::Point};
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(2024, 14, params = Params);
+
+advent_of_code::params! {
+    pub struct Params {
+        width: isize = 101,
+        height: isize = 103,
+        seconds: u32 = 100,
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Robot {
@@ -69,20 +77,20 @@ fn solve_p1(configuration: Configuration) -> u32 {
     result.0 * result.1 * result.2 * result.3
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
     let configuration = Configuration {
-        seconds: 100,
-        grid_size: Point::new(103, 101),
+        seconds: params.seconds,
+        grid_size: Point::new(params.height, params.width),
         robots: parse_input(input),
     };
 
     Some(solve_p1(configuration))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str, params: &Params) -> Option<u32> {
     let mut configuration = Configuration {
-        seconds: 100,
-        grid_size: Point::new(103, 101),
+        seconds: params.seconds,
+        grid_size: Point::new(params.height, params.width),
         robots: parse_input(input),
     };
 
@@ -151,14 +159,8 @@ mod tests {
         assert_eq!(result, 0);
     }
 
-    #[test]
-    fn test_part_one() {
-        let configuration = Configuration {
-            seconds: 100,
-            grid_size: Point::new(7, 11),
-            robots: parse_input(&advent_of_code::template::read_file("examples", DAY)),
-        };
-        let result = solve_p1(configuration);
-        assert_eq!(result, 12);
+    advent_of_code::examples! {
+        params = Params,
+        _ => (Some(12), None),
     }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::collections::Bounded;
 use heapless::Vec as HeaplessVec;
 use itertools::Itertools;
 use mygrid::{
@@ -6,10 +7,12 @@ use mygrid::{
     point::Point,
 };
 
-advent_of_code::solution!(15);
+advent_of_code::solution!(2024, 15, parse = parse_input);
+
+type Warehouse = (Grid<char>, Point, Vec<Direction>);
 
 #[inline]
-fn parse_input(input: &str) -> (Grid<char>, Point, Vec<Direction>) {
+fn parse_input(input: &str) -> Warehouse {
     let (grid_str, path_str) = input.split_once("\n\n").unwrap();
     let grid = Grid::new_char_grid_from_str(grid_str);
     let start = grid
@@ -25,11 +28,11 @@ fn parse_input(input: &str) -> (Grid<char>, Point, Vec<Direction>) {
     (grid, start, path)
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (mut grid, start, path) = parse_input(input);
+pub fn part_one((grid, start, path): &Warehouse) -> Option<u32> {
+    let mut grid = grid.clone();
 
-    let mut robot_pos = start;
-    for direction in path {
+    let mut robot_pos = *start;
+    for &direction in path {
         //dbg!(&grid, &robot_pos, &direction.to_string());
         // find the first hole
         let mut lookup_pos = robot_pos;
@@ -58,11 +61,9 @@ pub fn part_one(input: &str) -> Option<u32> {
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (base_grid, base_start, path) = parse_input(input);
-
+pub fn part_two((base_grid, base_start, path): &Warehouse) -> Option<u32> {
     // transform grid for part 2
-    let mut start: Point = base_start;
+    let mut start: Point = *base_start;
     let mut grid = Grid::new(base_grid.width * 2, base_grid.height, '.');
     base_grid.iter_item_and_position().for_each(|(p, v)| {
         let p = Point::new(p.line, p.column * 2);
@@ -77,7 +78,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     });
 
     let mut robot_pos = start;
-    for direction in path {
+    for &direction in path {
         // fast path when the next position is a hole
         let next_pos = robot_pos + direction;
         if grid[next_pos] == '.' {
@@ -144,8 +145,8 @@ pub fn part_two(input: &str) -> Option<u32> {
         type LookupPos = HeaplessVec<Point, 128>;
         let mut lookup_steps: LookupSteps = LookupSteps::new();
         let mut lookup_pos: LookupPos = LookupPos::new();
-        lookup_pos.push(robot_pos).unwrap();
-        lookup_steps.push(lookup_pos.clone()).unwrap();
+        lookup_pos.try_insert(robot_pos).unwrap();
+        lookup_steps.try_insert(lookup_pos.clone()).unwrap();
         let mut can_move = true;
 
         while lookup_pos.len() > 0 && can_move {
@@ -161,17 +162,17 @@ pub fn part_two(input: &str) -> Option<u32> {
                 let next_pos = p + direction;
                 match grid[next_pos] {
                     '[' => {
-                        next_lookup_pos.push(next_pos).unwrap();
-                        next_lookup_pos.push(next_pos + RIGHT).unwrap();
+                        next_lookup_pos.try_insert(next_pos).unwrap();
+                        next_lookup_pos.try_insert(next_pos + RIGHT).unwrap();
                     }
                     ']' => {
-                        next_lookup_pos.push(next_pos).unwrap();
-                        next_lookup_pos.push(next_pos + LEFT).unwrap();
+                        next_lookup_pos.try_insert(next_pos).unwrap();
+                        next_lookup_pos.try_insert(next_pos + LEFT).unwrap();
                     }
-                    _ => next_lookup_pos.push(next_pos).unwrap(),
+                    _ => next_lookup_pos.try_insert(next_pos).unwrap(),
                 }
             }
-            lookup_steps.push(next_lookup_pos.clone()).unwrap();
+            lookup_steps.try_insert(next_lookup_pos.clone()).unwrap();
             lookup_pos = next_lookup_pos;
         }
 
@@ -203,35 +204,10 @@ pub fn part_two(input: &str) -> Option<u32> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one_1() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(2028));
-    }
-
-    #[test]
-    fn test_part_one_2() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(10092));
-    }
-
-    #[test]
-    fn test_part_two_1() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
-        ));
-        assert_eq!(result, Some(618));
-    }
-
-    #[test]
-    fn test_part_two_2() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(9021));
+    advent_of_code::examples! {
+        parse = parse_input,
+        1 => (Some(2028), None),
+        2 => (Some(10092), Some(9021)),
+        3 => (None, Some(618)),
     }
 }
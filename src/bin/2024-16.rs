@@ -6,7 +6,7 @@ use mygrid::{
     point::Point,
 };
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(2024, 16);
 
 #[inline]
 fn dir_to_index(dir: Direction) -> usize {
@@ -138,51 +138,9 @@ pub fn part_two(input: &str) -> Option<i64> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one_1() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(7036));
-    }
-
-    #[test]
-    fn test_part_one_2() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(11048));
-    }
-
-    #[test]
-    fn test_part_one_3() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
-        ));
-        assert_eq!(result, Some(9024));
-    }
-
-    #[test]
-    fn test_part_two_1() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(45));
-    }
-
-    #[test]
-    fn test_part_two_2() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(64));
-    }
-
-    #[test]
-    fn test_part_two_3() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
-        ));
-        assert_eq!(result, Some(34));
+    advent_of_code::examples! {
+        1 => (Some(7036), Some(45)),
+        2 => (Some(11048), Some(64)),
+        3 => (Some(9024), Some(34)),
     }
 }
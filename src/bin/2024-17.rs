@@ -1,4 +1,6 @@
-advent_of_code::solution!(17);
+use advent_of_code::template::generate::Rng;
+
+advent_of_code::solution!(2024, 17, generate = generate);
 
 #[derive(Debug, Clone)]
 struct Machine {
@@ -161,10 +163,80 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(reg_a)
 }
 
+/// Finds the lowest value of register A that makes the program output itself, building it up 3 bits at a time.
+fn find_quine(machine: &Machine, reg_a: u64, take_len: usize) -> Option<u64> {
+    let len = machine.tape.len();
+    if take_len > len {
+        return Some(reg_a);
+    }
+
+    (0..8).find_map(|bits| {
+        let new_a = (reg_a << 3) | bits;
+        let mut m = machine.clone();
+        m.reg_a = new_a;
+        while m.step() {}
+
+        (m.output == machine.tape[len - take_len..])
+            .then(|| find_quine(machine, new_a, take_len + 1))
+            .flatten()
+    })
+}
+
+/// Generates a program shaped like the puzzle inputs: it outputs a function of the lowest 3 bits of A and shifts A right by 3, until A is 0.
+/// Only programs that can output themselves are returned. Register A has `size` octal digits.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let digits = size.clamp(1, 21) as u32;
+    let reg_a = rng.range(8_u64.pow(digits - 1), 8_u64.pow(digits));
+
+    loop {
+        let mut middle = [[1, rng.range(0, 8)], [4, rng.range(0, 8)], [0, 3]];
+        rng.shuffle(&mut middle);
+
+        let mut tape = vec![2, 4, 1, rng.range(0, 8), 7, 5];
+        tape.extend(middle.iter().flatten());
+        tape.extend([5, 5, 3, 0]);
+
+        let machine = Machine {
+            reg_a: 0,
+            reg_b: 0,
+            reg_c: 0,
+            ip: 0,
+            tape,
+            output: vec![],
+        };
+
+        if find_quine(&machine, 0, 1).is_some() {
+            let program = machine
+                .tape
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>();
+            return format!(
+                "Register A: {reg_a}\nRegister B: 0\nRegister C: 0\n\nProgram: {}\n",
+                program.join(",")
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    advent_of_code::examples! {
+        1 => (Some("4,6,3,5,6,3,5,2,1,0".to_string()), None),
+        2 => (None, Some(117440)),
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(3), 4);
+        let machine = Machine::parse(&input);
+        assert!((512..4096).contains(&machine.reg_a));
+        assert_eq!(machine.tape.len(), 16);
+        assert_eq!(part_two(&input), find_quine(&machine, 0, 1));
+    }
+
     #[test]
     fn test_ex_1() {
         let mut machine = Machine {
@@ -249,20 +321,4 @@ mod tests {
         while machine.step() {}
         assert_eq!(machine.output, vec![0, 3, 5, 4, 3, 0]);
     }
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some("4,6,3,5,6,3,5,2,1,0".to_string()));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(117440));
-    }
 }
@@ -2,7 +2,15 @@ use std::{collections::VecDeque, u32};
 
 use mygrid::{direction::ORTHOGONAL, grid::Grid, point::Point};
 
-advent_of_code::solution!(18);
+advent_of_code::solution!(2024, 18, params = Params);
+
+advent_of_code::params! {
+    pub struct Params {
+        width: usize = 71,
+        height: usize = 71,
+        bytes: usize = 1024,
+    }
+}
 
 #[inline]
 fn parse_points<'a>(input: &'a str) -> impl Iterator<Item = Point> + 'a {
@@ -53,8 +61,8 @@ fn find_shortest_path(input: &str, width: usize, height: usize, take: usize) ->
     Some(min_dst[end])
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    find_shortest_path(input, 71, 71, 1024)
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
+    find_shortest_path(input, params.width, params.height, params.bytes)
 }
 
 // work in reverse, fill them all in and find the first point that makes it possible to reach the end
@@ -99,8 +107,8 @@ fn find_cutoff(input: &str, width: usize, height: usize, _initial_take: usize) -
     })
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let cutoff = find_cutoff(input, 71, 71, 1024);
+pub fn part_two(input: &str, params: &Params) -> Option<String> {
+    let cutoff = find_cutoff(input, params.width, params.height, params.bytes);
     cutoff.map(|p| format!("{},{}", p.column, p.line))
 }
 
@@ -108,25 +116,8 @@ pub fn part_two(input: &str) -> Option<String> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = find_shortest_path(
-            &advent_of_code::template::read_file("examples", DAY),
-            7,
-            7,
-            12,
-        );
-        assert_eq!(result, Some(22));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = find_cutoff(
-            &advent_of_code::template::read_file("examples", DAY),
-            7,
-            7,
-            12,
-        );
-        assert_eq!(result, Some(Point::new(1, 6)));
+    advent_of_code::examples! {
+        params = Params,
+        _ => (Some(22), Some("6,1".to_string())),
     }
 }
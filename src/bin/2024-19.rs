@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 
+use advent_of_code::template::generate::Rng;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-advent_of_code::solution!(19);
+advent_of_code::solution!(2024, 19, generate = generate);
 
 #[inline]
 fn char_to_idx(c: char) -> usize {
@@ -162,19 +163,62 @@ pub fn part_two(input: &str) -> Option<u64> {
     Some(valid_count)
 }
 
+/// Number of designs in a generated input.
+const GENERATED_DESIGNS: usize = 10;
+
+/// Generates towels and designs of `size` stripes. One colour has no single-stripe towel, so some designs are impossible.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const COLOURS: &[u8] = b"wubrg";
+    let missing = *rng.pick(COLOURS);
+
+    let mut towels: Vec<String> = COLOURS
+        .iter()
+        .filter(|&&c| c != missing)
+        .map(|&c| char::from(c).to_string())
+        .collect();
+    while towels.len() < 40 {
+        let len = rng.range(2, 7) as usize;
+        let towel: String = (0..len).map(|_| char::from(*rng.pick(COLOURS))).collect();
+        if !towels.contains(&towel) {
+            towels.push(towel);
+        }
+    }
+    rng.shuffle(&mut towels);
+
+    let designs: Vec<String> = (0..GENERATED_DESIGNS)
+        .map(|_| {
+            (0..size.max(1))
+                .map(|_| char::from(*rng.pick(COLOURS)))
+                .collect()
+        })
+        .collect();
+
+    format!("{}\n\n{}\n", towels.join(", "), designs.join("\n"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(16));
     }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(2), 12);
+        let (trie, designs) = parse_input(&input);
+        assert_eq!(designs.len(), GENERATED_DESIGNS);
+        assert!(designs.iter().all(|d| d.len() == 12));
+        assert!(part_one(&input) <= part_two(&input));
+        assert!(trie.max_len <= 6);
+    }
 }
@@ -5,7 +5,13 @@ use mygrid::{
 };
 use rayon::iter::ParallelIterator;
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(2024, 20, params = Params);
+
+advent_of_code::params! {
+    pub struct Params {
+        min_gain: i64 = 100,
+    }
+}
 
 #[inline]
 fn parse_input(input: &str) -> (Grid<char>, Point, Point) {
@@ -88,78 +94,72 @@ fn solve<const CHEAT_MOVES: isize>(input: &str, min_gain: i64) -> Option<i64> {
     Some(count)
 }
 
-pub fn part_one(input: &str) -> Option<i64> {
-    solve::<2>(input, 100)
+pub fn part_one(input: &str, params: &Params) -> Option<i64> {
+    solve::<2>(input, params.min_gain)
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    solve::<20>(input, 100)
+pub fn part_two(input: &str, params: &Params) -> Option<i64> {
+    solve::<20>(input, params.min_gain)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::{parse_params, read_file};
+
+    advent_of_code::examples! {
+        params = Params,
+        _ => (Some(1), Some(86)),
+    }
+
+    fn solve_example(func: fn(&str, &Params) -> Option<i64>, min_gain: i64) -> Option<i64> {
+        let input = read_file("examples", PUZZLE);
+        let (_, input) = parse_params::<Params, &str>(&input, &[]).unwrap();
+        func(input, &Params { min_gain })
+    }
 
     #[test]
     fn test_part_one_0() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        let result = solve::<2>(&input, 1);
-        assert_eq!(result, Some(44));
+        assert_eq!(solve_example(part_one, 1), Some(44));
     }
 
     #[test]
     fn test_part_one_64() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        let result = solve::<2>(&input, 64);
-        assert_eq!(result, Some(1));
+        assert_eq!(solve_example(part_one, 64), Some(1));
     }
 
     #[test]
     fn test_part_one_40() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        let result = solve::<2>(&input, 40);
-        assert_eq!(result, Some(2));
+        assert_eq!(solve_example(part_one, 40), Some(2));
     }
 
     #[test]
     fn test_part_one_20() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        let result = solve::<2>(&input, 20);
-        assert_eq!(result, Some(5));
+        assert_eq!(solve_example(part_one, 20), Some(5));
     }
 
     #[test]
     fn test_part_two_50() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        let result = solve::<20>(&input, 50);
-        assert_eq!(result, Some(285));
+        assert_eq!(solve_example(part_two, 50), Some(285));
     }
 
     #[test]
     fn test_part_two_64() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        let result = solve::<20>(&input, 64);
-        assert_eq!(result, Some(86));
+        assert_eq!(solve_example(part_two, 64), Some(86));
     }
 
     #[test]
     fn test_part_two_70() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        let result = solve::<20>(&input, 70);
-        assert_eq!(result, Some(41));
+        assert_eq!(solve_example(part_two, 70), Some(41));
     }
 
     #[test]
     fn test_part_two_74() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        let result = solve::<20>(&input, 74);
-        assert_eq!(result, Some(7));
+        assert_eq!(solve_example(part_two, 74), Some(7));
     }
 
     #[test]
     fn test_part_two_76() {
-        let input = advent_of_code::template::read_file("examples", DAY);
-        let result = solve::<20>(&input, 76);
-        assert_eq!(result, Some(3));
+        assert_eq!(solve_example(part_two, 76), Some(3));
     }
 }
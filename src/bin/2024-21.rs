@@ -4,7 +4,7 @@ use itertools::Itertools;
 use memoize::{lazy_static::lazy_static, memoize};
 use rustc_hash::FxHashMap;
 
-advent_of_code::solution!(21);
+advent_of_code::solution!(2024, 21);
 
 lazy_static! {
     static ref SUBST: FxHashMap<(char, char), String> = {
@@ -216,13 +216,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(126384));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(154115708116294));
     }
 }
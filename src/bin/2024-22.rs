@@ -1,7 +1,8 @@
+use advent_of_code::template::generate::Rng;
 use bitvec::bitvec;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-advent_of_code::solution!(22);
+advent_of_code::solution!(2024, 22, generate = generate, reference = reference);
 
 type Secret = u32;
 
@@ -93,9 +94,67 @@ pub fn part_two(input: &str) -> Option<i32> {
     Some(max_bananas)
 }
 
+/// Naive solution: follows the puzzle text literally and keeps the prices of every sequence in a map.
+mod reference {
+    use std::collections::{HashMap, HashSet};
+
+    fn next_secret(secret: u64) -> u64 {
+        let mix_and_prune = |value: u64, secret: u64| (value ^ secret) % 16777216;
+        let secret = mix_and_prune(secret * 64, secret);
+        let secret = mix_and_prune(secret / 32, secret);
+        mix_and_prune(secret * 2048, secret)
+    }
+
+    fn secrets(input: &str) -> impl Iterator<Item = Vec<u64>> + '_ {
+        input.lines().filter(|l| !l.is_empty()).map(|l| {
+            let mut secrets = vec![l.parse().unwrap()];
+            for _ in 0..2000 {
+                secrets.push(next_secret(*secrets.last().unwrap()));
+            }
+            secrets
+        })
+    }
+
+    pub fn part_one(input: &str) -> Option<u64> {
+        Some(secrets(input).map(|s| s[2000]).sum())
+    }
+
+    pub fn part_two(input: &str) -> Option<i32> {
+        let mut bananas: HashMap<Vec<i32>, i32> = HashMap::new();
+
+        for secrets in secrets(input) {
+            let prices: Vec<i32> = secrets.iter().map(|s| (s % 10) as i32).collect();
+            let changes: Vec<i32> = prices.windows(2).map(|w| w[1] - w[0]).collect();
+
+            let mut seen = HashSet::new();
+            for (i, sequence) in changes.windows(4).enumerate() {
+                if seen.insert(sequence.to_vec()) {
+                    *bananas.entry(sequence.to_vec()).or_default() += prices[i + 4];
+                }
+            }
+        }
+
+        bananas.into_values().max()
+    }
+}
+
+/// Generates `size` buyers with random initial secrets.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| format!("{}\n", rng.range(1, 1 << 24)))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    advent_of_code::examples! {
+        1 => (Some(37327623), None),
+        2 => (None, Some(9)),
+        3 => (None, Some(23)),
+    }
+
     #[test]
     fn test_next_secrets() {
         let seed = 123;
@@ -109,27 +168,5 @@ mod tests {
         .for_each(|(i, &expected)| assert_eq!(get_secret(seed, i + 1), expected));
     }
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(37327623));
-    }
-
-    #[test]
-    fn test_part_two_0() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(9));
-    }
-
-    #[test]
-    fn test_part_two_1() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 3,
-        ));
-        assert_eq!(result, Some(23));
-    }
+    advent_of_code::differential!(generate = generate);
 }
@@ -2,7 +2,7 @@ use itertools::Itertools;
 use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
 use rustc_hash::{FxHashMap, FxHashSet};
 
-advent_of_code::solution!(23);
+advent_of_code::solution!(2024, 23, parse = parse_input);
 
 type Node = (u8, u8);
 type Connections = FxHashMap<Node, FxHashSet<Node>>;
@@ -26,9 +26,7 @@ fn parse_input(input: &str) -> Connections {
     connections
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let graph = parse_input(input);
-
+pub fn part_one(graph: &Connections) -> Option<u32> {
     type Island = (Node, Node, Node);
 
     let all_islands = graph
@@ -48,8 +46,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(islands.len() as u32)
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let graph = parse_input(input);
+pub fn part_two(graph: &Connections) -> Option<String> {
     let mut cliques = graph
         .keys()
         .map(|&a| FxHashSet::from_iter([a]))
@@ -77,15 +74,8 @@ pub fn part_two(input: &str) -> Option<String> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(7));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("co,de,ka,ta".to_string()));
+    advent_of_code::examples! {
+        parse = parse_input,
+        _ => (Some(7), Some("co,de,ka,ta".to_string())),
     }
 }
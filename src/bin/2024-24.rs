@@ -3,11 +3,11 @@ use std::collections::{HashSet, VecDeque};
 use itertools::Itertools;
 use rustc_hash::FxHashMap;
 
-advent_of_code::solution!(24);
+advent_of_code::solution!(2024, 24, parse = parse_input);
 
 type Wire = (u8, u8, u8);
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Op {
+pub enum Op {
     And,
     Or,
     Xor,
@@ -106,10 +106,10 @@ fn solve(connections: &Connections, values: &mut Values) -> u64 {
     res
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (connections, mut values) = parse_input(input);
+pub fn part_one((connections, values): &(Connections, Values)) -> Option<u64> {
+    let mut values = values.clone();
 
-    Some(solve(&connections, &mut values))
+    Some(solve(connections, &mut values))
 }
 
 #[allow(unused)]
@@ -143,9 +143,7 @@ fn print_dot(connections: &Connections, deviating_nodes: &HashSet<Wire>) {
     println!("{}", s);
 }
 
-pub fn part_two(input: &str) -> Option<String> {
-    let (connections, _) = parse_input(input);
-
+pub fn part_two((connections, _): &(Connections, Values)) -> Option<String> {
     let mut deviating_nodes = HashSet::new();
 
     for z_node in connections.keys().filter(|(a, _, _)| *a == b'z') {
@@ -186,19 +184,9 @@ pub fn part_two(input: &str) -> Option<String> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_part_one_1() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(4));
-    }
-
-    #[test]
-    fn test_part_one_2() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(2024));
+    advent_of_code::examples! {
+        parse = parse_input,
+        1 => (Some(4), None),
+        2 => (Some(2024), None),
     }
 }
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(25);
+advent_of_code::solution!(2024, 25);
 
 const WIDTH: usize = 5;
 const HEIGHT: usize = 6;
@@ -59,13 +59,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(3));
     }
 }
@@ -0,0 +1,405 @@
+/// Fixed-capacity equivalents of the `itertools` operations used by the solutions, built on `heapless`.
+/// Every insertion is checked: a full collection returns a [`CapacityError`] naming the collection and its capacity,
+/// instead of handing back the bare value like `heapless` does.
+use std::any::type_name;
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+use std::hash::{BuildHasher, Hash};
+
+use heapless::binary_heap::{Kind, Min};
+use heapless::{BinaryHeap, IndexMap, IndexSet};
+
+pub use heapless::{Deque, FnvIndexMap, FnvIndexSet, Vec as HeaplessVec};
+
+/// A fixed-capacity collection was full. Unwrapping it panics with e.g.
+/// `heapless::vec::Vec<u8, 128> is full (capacity 128), could not insert 17`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct CapacityError<T> {
+    /// Type name of the collection, including its capacity.
+    pub collection: &'static str,
+    pub capacity: usize,
+    /// The item that did not fit.
+    pub value: T,
+}
+
+impl<T> CapacityError<T> {
+    fn of<C: ?Sized>(capacity: usize, value: T) -> Self {
+        CapacityError {
+            collection: type_name::<C>(),
+            capacity,
+            value,
+        }
+    }
+
+    /// Replaces the item, e.g. to report a whole entry instead of the part that did not fit.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> CapacityError<U> {
+        CapacityError {
+            collection: self.collection,
+            capacity: self.capacity,
+            value: f(self.value),
+        }
+    }
+}
+
+impl<T: Debug> Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is full (capacity {}), could not insert {:?}",
+            self.collection, self.capacity, self.value
+        )
+    }
+}
+
+// `.unwrap()` prints the debug representation, so it reads the same as the message.
+impl<T: Debug> Debug for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl<T: Debug> Error for CapacityError<T> {}
+
+/* -------------------------------------------------------------------------- */
+
+/// A collection of fixed capacity that items can be inserted into.
+pub trait Bounded<T>: Default {
+    /// The maximum number of items.
+    const CAPACITY: usize;
+
+    /// Inserts an item, handing it back if the collection is full.
+    fn insert_item(&mut self, item: T) -> Result<(), T>;
+
+    /// Inserts an item, failing with an error that names the collection if it is full.
+    fn try_insert(&mut self, item: T) -> Result<(), CapacityError<T>> {
+        self.insert_item(item)
+            .map_err(|item| CapacityError::of::<Self>(Self::CAPACITY, item))
+    }
+}
+
+impl<T, const N: usize> Bounded<T> for HeaplessVec<T, N> {
+    const CAPACITY: usize = N;
+
+    fn insert_item(&mut self, item: T) -> Result<(), T> {
+        self.push(item)
+    }
+}
+
+/// Items are pushed to the back.
+impl<T, const N: usize> Bounded<T> for Deque<T, N> {
+    const CAPACITY: usize = N;
+
+    fn insert_item(&mut self, item: T) -> Result<(), T> {
+        self.push_back(item)
+    }
+}
+
+impl<T, S, const N: usize> Bounded<T> for IndexSet<T, S, N>
+where
+    T: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    const CAPACITY: usize = N;
+
+    fn insert_item(&mut self, item: T) -> Result<(), T> {
+        self.insert(item).map(|_| ())
+    }
+}
+
+/// Inserting an existing key replaces its value.
+impl<K, V, S, const N: usize> Bounded<(K, V)> for IndexMap<K, V, S, N>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    const CAPACITY: usize = N;
+
+    fn insert_item(&mut self, (key, value): (K, V)) -> Result<(), (K, V)> {
+        self.insert(key, value).map(|_| ())
+    }
+}
+
+impl<T: Ord, K: Kind, const N: usize> Bounded<T> for BinaryHeap<T, K, N> {
+    const CAPACITY: usize = N;
+
+    fn insert_item(&mut self, item: T) -> Result<(), T> {
+        self.push(item)
+    }
+}
+
+/// Collects iterators into fixed-capacity collections.
+pub trait TryCollect: Iterator + Sized {
+    /// Collects all items, failing on the first one that does not fit.
+    /// It is named like `Itertools::try_collect`, so only import one of both traits in a solution.
+    fn try_collect<C: Bounded<Self::Item>>(self) -> Result<C, CapacityError<Self::Item>> {
+        let mut collection = C::default();
+        for item in self {
+            collection.try_insert(item)?;
+        }
+        Ok(collection)
+    }
+}
+
+impl<I: Iterator> TryCollect for I {}
+
+/* -------------------------------------------------------------------------- */
+
+/// Counts how often each item occurs, like `Itertools::counts`. Fails if there are more than `N` distinct items.
+pub fn counts<K: Eq + Hash, const N: usize>(
+    iter: impl IntoIterator<Item = K>,
+) -> Result<FnvIndexMap<K, usize, N>, CapacityError<K>> {
+    let mut counts = FnvIndexMap::new();
+    for key in iter {
+        match counts.get_mut(&key) {
+            Some(count) => *count += 1,
+            None => counts
+                .try_insert((key, 1))
+                .map_err(|e| e.map(|(key, _)| key))?,
+        }
+    }
+    Ok(counts)
+}
+
+/// Values grouped by key, as returned by [`group_map`].
+pub type GroupMap<K, V, const M: usize, const N: usize> = FnvIndexMap<K, HeaplessVec<V, M>, N>;
+
+/// Groups values by their key in the order they appear, like `Itertools::into_group_map`.
+/// Fails if there are more than `N` keys, or more than `M` values for a key.
+pub fn group_map<K: Eq + Hash, V, const M: usize, const N: usize>(
+    iter: impl IntoIterator<Item = (K, V)>,
+) -> Result<GroupMap<K, V, M, N>, CapacityError<(K, V)>> {
+    let mut groups = GroupMap::new();
+    for (key, value) in iter {
+        if let Some(group) = groups.get_mut(&key) {
+            group.try_insert(value).map_err(|e| e.map(|v| (key, v)))?;
+            continue;
+        }
+
+        let mut group = HeaplessVec::new();
+        if let Err(e) = group.try_insert(value) {
+            return Err(e.map(|v| (key, v)));
+        }
+        groups.try_insert((key, group)).map_err(|e| {
+            e.map(|(key, mut group)| (key, group.pop().expect("the group holds the value")))
+        })?;
+    }
+    Ok(groups)
+}
+
+/// Collects and sorts the items, like `Itertools::sorted`. The sort is not stable, a stable sort would allocate.
+pub fn sorted<T: Ord, const N: usize>(
+    iter: impl IntoIterator<Item = T>,
+) -> Result<HeaplessVec<T, N>, CapacityError<T>> {
+    let mut items: HeaplessVec<T, N> = iter.into_iter().try_collect()?;
+    items.sort_unstable();
+    Ok(items)
+}
+
+/// Every pair of items `(a, b)` where `a` comes before `b`, like `Itertools::tuple_combinations` for pairs.
+/// The items are buffered, which fails if there are more than `N`.
+pub fn tuple_combinations<T: Clone, const N: usize>(
+    iter: impl IntoIterator<Item = T>,
+) -> Result<TupleCombinations<T, N>, CapacityError<T>> {
+    Ok(TupleCombinations {
+        items: iter.into_iter().try_collect()?,
+        first: 0,
+        second: 1,
+    })
+}
+
+/// Iterator returned by [`tuple_combinations`].
+#[derive(Clone, Debug)]
+pub struct TupleCombinations<T, const N: usize> {
+    items: HeaplessVec<T, N>,
+    first: usize,
+    second: usize,
+}
+
+impl<T: Clone, const N: usize> Iterator for TupleCombinations<T, N> {
+    type Item = (T, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.second >= self.items.len() {
+            self.first += 1;
+            self.second = self.first + 1;
+        }
+
+        let pair = (
+            self.items.get(self.first)?.clone(),
+            self.items.get(self.second)?.clone(),
+        );
+        self.second += 1;
+        Some(pair)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An item with its priority, ordered by the priority alone.
+#[derive(Clone, Debug)]
+struct Entry<P, T> {
+    priority: P,
+    item: T,
+}
+
+impl<P: Ord, T> PartialEq for Entry<P, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<P: Ord, T> Eq for Entry<P, T> {}
+
+impl<P: Ord, T> PartialOrd for Entry<P, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: Ord, T> Ord for Entry<P, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority)
+    }
+}
+
+/// A queue of fixed capacity that pops the item with the lowest priority first, e.g. for Dijkstra.
+/// Unlike a `BinaryHeap`, only the priorities need to be ordered, not the items.
+pub struct PriorityQueue<P, T, const N: usize> {
+    heap: BinaryHeap<Entry<P, T>, Min, N>,
+}
+
+impl<P: Ord, T, const N: usize> PriorityQueue<P, T, N> {
+    pub fn new() -> Self {
+        PriorityQueue {
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    pub fn push(&mut self, priority: P, item: T) -> Result<(), CapacityError<(P, T)>> {
+        self.try_insert((priority, item))
+    }
+
+    /// Removes the item with the lowest priority.
+    pub fn pop(&mut self) -> Option<(P, T)> {
+        self.heap.pop().map(|e| (e.priority, e.item))
+    }
+
+    /// Returns the item with the lowest priority.
+    pub fn peek(&self) -> Option<(&P, &T)> {
+        self.heap.peek().map(|e| (&e.priority, &e.item))
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+}
+
+impl<P: Ord, T, const N: usize> Default for PriorityQueue<P, T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Ord, T, const N: usize> Bounded<(P, T)> for PriorityQueue<P, T, N> {
+    const CAPACITY: usize = N;
+
+    fn insert_item(&mut self, (priority, item): (P, T)) -> Result<(), (P, T)> {
+        self.heap
+            .push(Entry { priority, item })
+            .map_err(|e| (e.priority, e.item))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        counts, group_map, sorted, tuple_combinations, Bounded, Deque, HeaplessVec, PriorityQueue,
+        TryCollect,
+    };
+
+    #[test]
+    fn collects_until_full() {
+        let items: HeaplessVec<u8, 4> = (1..=4).try_collect().unwrap();
+        assert_eq!(items, [1, 2, 3, 4]);
+
+        let error = (1..=5).try_collect::<HeaplessVec<u8, 4>>().unwrap_err();
+        assert_eq!((error.capacity, error.value), (4, 5));
+        assert_eq!(
+            error.to_string(),
+            "heapless::vec::Vec<u8, 4> is full (capacity 4), could not insert 5"
+        );
+        assert_eq!(format!("{error:?}"), error.to_string());
+    }
+
+    #[test]
+    fn counts_items() {
+        let counted = counts::<_, 4>("abcab".chars()).unwrap();
+        let counted: Vec<(char, usize)> = counted.iter().map(|(&c, &n)| (c, n)).collect();
+        assert_eq!(counted, vec![('a', 2), ('b', 2), ('c', 1)]);
+
+        assert_eq!(counts::<_, 2>("abc".chars()).unwrap_err().value, 'c');
+    }
+
+    #[test]
+    fn groups_values() {
+        let groups = group_map::<_, _, 2, 4>([('a', 1), ('b', 2), ('a', 3)]).unwrap();
+        assert_eq!(groups[&'a'], [1, 3]);
+        assert_eq!(groups[&'b'], [2]);
+
+        // too many values for a key, then too many keys.
+        let error = group_map::<_, _, 2, 4>([('a', 1), ('a', 2), ('a', 3)]).unwrap_err();
+        assert_eq!((error.capacity, error.value), (2, ('a', 3)));
+        let error = group_map::<_, _, 2, 2>([('a', 1), ('b', 2), ('c', 3)]).unwrap_err();
+        assert_eq!((error.capacity, error.value), (2, ('c', 3)));
+        assert!(error.collection.contains("IndexMap"));
+    }
+
+    #[test]
+    fn pairs_items() {
+        let pairs: Vec<_> = tuple_combinations::<_, 4>("abc".chars()).unwrap().collect();
+        assert_eq!(pairs, vec![('a', 'b'), ('a', 'c'), ('b', 'c')]);
+        assert_eq!(tuple_combinations::<u8, 4>([]).unwrap().count(), 0);
+        assert_eq!(tuple_combinations::<u8, 4>([1]).unwrap().count(), 0);
+        assert!(tuple_combinations::<_, 2>([1, 2, 3]).is_err());
+    }
+
+    #[test]
+    fn sorts_items() {
+        assert_eq!(sorted::<_, 8>([3, 1, 2]).unwrap(), [1, 2, 3]);
+        assert_eq!(sorted::<_, 2>([3, 1, 2]).unwrap_err().value, 2);
+    }
+
+    #[test]
+    fn pops_lowest_priority_first() {
+        // the items themselves are not ordered.
+        let mut queue: PriorityQueue<u32, fn() -> u8, 2> = PriorityQueue::new();
+        queue.push(5, || 5).unwrap();
+        queue.push(1, || 1).unwrap();
+        assert_eq!(queue.push(3, || 3).unwrap_err().value.0, 3);
+
+        assert_eq!(queue.peek().map(|(p, _)| *p), Some(1));
+        assert_eq!(queue.pop().map(|(p, item)| (p, item())), Some((1, 1)));
+        assert_eq!(queue.pop().map(|(p, item)| (p, item())), Some((5, 5)));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn pushes_to_back_of_deque() {
+        let mut deque: Deque<u8, 2> = [1, 2].into_iter().try_collect().unwrap();
+        assert_eq!(deque.pop_front(), Some(1));
+        deque.try_insert(3).unwrap();
+        assert_eq!(deque.try_insert(4).unwrap_err().capacity, 2);
+        assert_eq!(deque.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+    }
+}
@@ -1,45 +1,95 @@
-use advent_of_code::template::commands::{all, download, flame, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, flame, fuzz, leaderboard, read, scaffold, solve, time,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::PuzzleId;
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::counters::Metric;
+    use advent_of_code::template::{compare, Day, PuzzleId, PuzzleIdFromStrError, Year};
+    use std::path::PathBuf;
     use std::process;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Examples {
+            puzzle: PuzzleId,
+            overwrite: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
+            vars: Vec<(String, String)>,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
+            alloc: bool,
             submit: Option<u8>,
+            input: Option<String>,
+            profile: Option<String>,
+            params: Vec<String>,
+        },
+        SolveAllInputs {
+            puzzle: PuzzleId,
+            release: bool,
+        },
+        SolveWatch {
+            puzzle: PuzzleId,
+            release: bool,
+            input: Option<String>,
+            profile: Option<String>,
+            params: Vec<String>,
         },
         Flame {
-            day: Day,
+            puzzle: PuzzleId,
             debug: bool,
             dhat: bool,
+            part: Option<u8>,
+        },
+        FuzzDay {
+            puzzle: PuzzleId,
+            runs: Option<usize>,
+            size: Option<usize>,
+            seed: Option<u64>,
+            timeout: Option<u64>,
         },
         All {
+            year: Year,
             release: bool,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: Option<f64>,
+            alloc: bool,
+            metric: Metric,
+            html: Option<PathBuf>,
+        },
+        TimeScale {
+            puzzle: PuzzleId,
+            size: Option<usize>,
+            timeout: Option<u64>,
+        },
+        History {
+            puzzle: PuzzleId,
+        },
+        Leaderboard {
+            path: PathBuf,
         },
         #[cfg(feature = "today")]
         Today,
@@ -48,41 +98,108 @@ mod args {
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        let year: Option<Year> = args.opt_value_from_str("--year")?.or_else(Year::current);
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year: year.ok_or(PuzzleIdFromStrError::MissingYear)?,
                 release: args.contains("--release"),
             },
+            Some("time") if args.contains("--scale") => AppArguments::TimeScale {
+                puzzle: parse_puzzle(&mut args, year)?,
+                size: args.opt_value_from_str("--size")?,
+                timeout: args.opt_value_from_str("--timeout")?,
+            },
             Some("time") => {
+                if let Some(puzzle) = args
+                    .opt_value_from_str::<_, String>("--history")?
+                    .map(|s| PuzzleId::parse(&s, year))
+                    .transpose()?
+                {
+                    return finish(args, AppArguments::History { puzzle });
+                }
+
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let alloc = args.contains("--alloc");
+                let metric = args.opt_value_from_str("--metric")?;
+                let html = args.opt_value_from_str("--html")?;
+                let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+                let compare = args.contains("--compare") || threshold.is_some();
+
+                let puzzle = args
+                    .opt_free_from_str::<String>()?
+                    .map(|s| PuzzleId::parse(&s, year))
+                    .transpose()?;
 
                 AppArguments::Time {
+                    year: puzzle
+                        .map(|p| p.year)
+                        .or(year)
+                        .ok_or(PuzzleIdFromStrError::MissingYear)?,
                     all,
-                    day: args.opt_free_from_str()?,
+                    day: puzzle.map(|p| p.day),
                     store,
+                    compare: compare.then(|| threshold.unwrap_or(compare::DEFAULT_THRESHOLD)),
+                    alloc,
+                    metric: metric.unwrap_or(Metric::Time),
+                    html,
                 }
             }
+            Some("leaderboard") => AppArguments::Leaderboard {
+                path: args.free_from_str()?,
+            },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args, year)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args, year)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                puzzle: parse_puzzle(&mut args, year)?,
+                overwrite: args.contains("--overwrite"),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args, year)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
+                vars: args.values_from_fn("--var", parse_var)?,
             },
             Some("flame") => AppArguments::Flame {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args, year)?,
                 debug: args.contains("--debug"),
                 dhat: args.contains("--dhat"),
+                part: args.opt_value_from_str("--part")?,
+            },
+            Some("fuzz-day") => AppArguments::FuzzDay {
+                puzzle: parse_puzzle(&mut args, year)?,
+                runs: args.opt_value_from_str("--runs")?,
+                size: args.opt_value_from_str("--size")?,
+                seed: args.opt_value_from_str("--seed")?,
+                timeout: args.opt_value_from_str("--timeout")?,
+            },
+            Some("solve") if args.contains("--all-inputs") => AppArguments::SolveAllInputs {
+                puzzle: parse_puzzle(&mut args, year)?,
+                release: args.contains("--release"),
+            },
+            Some("solve") if args.contains("--watch") => AppArguments::SolveWatch {
+                puzzle: parse_puzzle(&mut args, year)?,
+                release: args.contains("--release"),
+                input: args.opt_value_from_str("--input")?,
+                profile: args.opt_value_from_str("--profile")?,
+                params: args.values_from_str("--param")?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args, year)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                alloc: args.contains("--alloc"),
+                input: args.opt_value_from_str("--input")?,
+                profile: args.opt_value_from_str("--profile")?,
+                params: args.values_from_str("--param")?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -96,6 +213,27 @@ mod args {
             }
         };
 
+        finish(args, app_args)
+    }
+
+    fn parse_puzzle(
+        args: &mut pico_args::Arguments,
+        year: Option<Year>,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let puzzle: String = args.free_from_str()?;
+        Ok(PuzzleId::parse(&puzzle, year)?)
+    }
+
+    fn parse_var(s: &str) -> Result<(String, String), String> {
+        s.split_once('=')
+            .map(|(k, v)| (k.trim().to_string(), v.to_string()))
+            .ok_or_else(|| format!("expected `--var key=value`, got `{s}`"))
+    }
+
+    fn finish(
+        args: pico_args::Arguments,
+        app_args: AppArguments,
+    ) -> Result<AppArguments, Box<dyn std::error::Error>> {
         let remaining = args.finish();
         if !remaining.is_empty() {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
@@ -112,39 +250,86 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold {
+            AppArguments::All { year, release } => all::handle(year, release),
+            AppArguments::Time {
+                year,
                 day,
+                all,
+                store,
+                compare,
+                alloc,
+                metric,
+                html,
+            } => time::handle(year, day, all, store, compare, alloc, metric, html),
+            AppArguments::TimeScale {
+                puzzle,
+                size,
+                timeout,
+            } => time::handle_scale(puzzle, size, timeout),
+            AppArguments::History { puzzle } => time::handle_history(puzzle),
+            AppArguments::Leaderboard { path } => leaderboard::handle(&path),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Examples { puzzle, overwrite } => examples::handle(puzzle, overwrite),
+            AppArguments::Scaffold {
+                puzzle,
                 download,
                 overwrite,
+                template,
+                vars,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(puzzle, overwrite, template.as_deref(), &vars);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
+                alloc,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
-            AppArguments::Flame { day, debug, dhat } => flame::handle(day, debug, dhat),
+                input,
+                profile,
+                params,
+            } => solve::handle(
+                puzzle, release, dhat, alloc, submit, input, profile, &params,
+            ),
+            AppArguments::SolveAllInputs { puzzle, release } => {
+                solve::handle_all_inputs(puzzle, release)
+            }
+            AppArguments::SolveWatch {
+                puzzle,
+                release,
+                input,
+                profile,
+                params,
+            } => solve::handle_watch(puzzle, release, input, profile, &params),
+            AppArguments::Flame {
+                puzzle,
+                debug,
+                dhat,
+                part,
+            } => flame::handle(puzzle, debug, dhat, part),
+            AppArguments::FuzzDay {
+                puzzle,
+                runs,
+                size,
+                seed,
+                timeout,
+            } => fuzz::handle(puzzle, runs, size, seed, timeout),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match PuzzleId::today() {
+                    Some(puzzle) => {
+                        scaffold::handle(puzzle, false, None, &[]);
+                        download::handle(puzzle);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run while the event is running. \
+                            Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
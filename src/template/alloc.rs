@@ -0,0 +1,142 @@
+/// A global allocator that counts heap allocations, used to report memory usage per part.
+/// Enabled with the `alloc-stats` feature, which the `solution!` macro picks up to install it.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static BASELINE: AtomicUsize = AtomicUsize::new(0);
+
+/// Forwards to the system allocator while keeping track of allocation counts and heap size.
+pub struct CountingAlloc;
+
+// SAFETY: every call is forwarded unchanged to the system allocator.
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // same as dhat, a reallocation counts as a new allocation of `new_size` bytes.
+            CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+/// Heap usage of a single solution part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations (including reallocations).
+    pub allocations: usize,
+    /// Total bytes requested over all allocations.
+    pub bytes: usize,
+    /// Highest heap size reached, relative to the heap size at [`reset`].
+    pub peak_bytes: usize,
+}
+
+/// Resets the counters. Memory that is already allocated is not counted towards the peak.
+pub fn reset() {
+    let current = CURRENT.load(Ordering::Relaxed);
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    BASELINE.store(current, Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+}
+
+/// Reads the counters accumulated since the last [`reset`].
+pub fn snapshot() -> AllocStats {
+    AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak_bytes: PEAK
+            .load(Ordering::Relaxed)
+            .saturating_sub(BASELINE.load(Ordering::Relaxed)),
+    }
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{} allocs, {} bytes, peak {} bytes]",
+            self.allocations, self.bytes, self.peak_bytes
+        )
+    }
+}
+
+impl AllocStats {
+    /// Parses the output of the [`Display`] implementation from a line of runner output.
+    pub fn parse(line: &str) -> Option<Self> {
+        let stats = line.rsplit('[').next()?.split(']').next()?;
+        if !stats.contains(" allocs, ") {
+            return None;
+        }
+
+        let mut numbers = stats
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|s| !s.is_empty())
+            .map(str::parse::<usize>);
+
+        let allocations = numbers.next()?.ok()?;
+        let bytes = numbers.next()?.ok()?;
+        let peak_bytes = numbers.next()?.ok()?;
+
+        Some(AllocStats {
+            allocations,
+            bytes,
+            peak_bytes,
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::AllocStats;
+
+    #[test]
+    fn parses_runner_output() {
+        let stats = AllocStats {
+            allocations: 12,
+            bytes: 3456,
+            peak_bytes: 2048,
+        };
+        let line = format!("Part 1: 42 (1.2ms @ 812 samples) {stats}");
+        assert_eq!(AllocStats::parse(&line), Some(stats));
+    }
+
+    #[test]
+    fn ignores_lines_without_stats() {
+        assert_eq!(AllocStats::parse("Part 1: 42 (1.2ms @ 812 samples)"), None);
+    }
+}
@@ -0,0 +1,225 @@
+/// Types a solution part can return, and the outcome of running a part.
+use std::any::Any;
+use std::error::Error;
+use std::fmt::Display;
+
+/// The outcome of running one part of a solution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// A single-line answer, which can be submitted.
+    Answer(String),
+    /// A multi-line answer, e.g. letters drawn on a grid. It needs to be read and submitted by hand.
+    MultiLine(String),
+    /// The part returned `None`.
+    Unsolved,
+    /// The part is not implemented yet.
+    Unimplemented,
+    /// The part returned an error. Contains the error, followed by its sources.
+    Failed(Vec<String>),
+    /// The part panicked. Contains the panic message.
+    Panicked(String),
+}
+
+impl Outcome {
+    /// Returns `true` if the part produced an answer.
+    pub fn is_solved(&self) -> bool {
+        matches!(self, Outcome::Answer(_) | Outcome::MultiLine(_))
+    }
+
+    /// Returns `true` if the part returned an error or panicked.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Outcome::Failed(_) | Outcome::Panicked(_))
+    }
+
+    fn from_display(value: impl Display) -> Self {
+        let answer = value.to_string();
+        if answer.contains('\n') {
+            Outcome::MultiLine(answer)
+        } else {
+            Outcome::Answer(answer)
+        }
+    }
+}
+
+/// Extracts the message of a panic from its payload.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".into())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Marks a part that is not implemented yet. Return it directly, or as the error of a [`Result`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unimplemented;
+
+impl Error for Unimplemented {}
+
+impl Display for Unimplemented {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("not implemented")
+    }
+}
+
+/// A multi-line answer, e.g. letters drawn with `#` on a grid.
+/// Trailing whitespace is removed from every line, and the answer is never submitted automatically.
+///
+/// ```
+/// # use advent_of_code::template::MultiLine;
+/// let answer = MultiLine::new("#..# \n#### \n");
+/// assert_eq!(answer.to_string(), "#..#\n####");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiLine(String);
+
+impl MultiLine {
+    pub fn new(answer: impl Into<String>) -> Self {
+        Self(
+            answer
+                .into()
+                .lines()
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+}
+
+impl Display for MultiLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The return type of a solution part: an [`Option`], a [`Result`], [`Unimplemented`] or a [`MultiLine`] answer.
+/// Answers can be of any type that implements [`Display`].
+pub trait PartResult {
+    fn into_outcome(self) -> Outcome;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn into_outcome(self) -> Outcome {
+        self.map_or(Outcome::Unsolved, Outcome::from_display)
+    }
+}
+
+impl<T: Display, E: Into<Box<dyn Error>>> PartResult for Result<T, E> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Ok(answer) => Outcome::from_display(answer),
+            Err(e) => {
+                let e: Box<dyn Error> = e.into();
+                if e.is::<Unimplemented>() {
+                    return Outcome::Unimplemented;
+                }
+
+                let mut chain = vec![e.to_string()];
+                let mut source = e.source();
+                while let Some(e) = source {
+                    chain.push(e.to_string());
+                    source = e.source();
+                }
+                Outcome::Failed(chain)
+            }
+        }
+    }
+}
+
+impl PartResult for Unimplemented {
+    fn into_outcome(self) -> Outcome {
+        Outcome::Unimplemented
+    }
+}
+
+impl PartResult for MultiLine {
+    fn into_outcome(self) -> Outcome {
+        Outcome::MultiLine(self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::error::Error;
+    use std::fmt::Display;
+    use std::panic;
+
+    use super::{panic_message, MultiLine, Outcome, PartResult, Unimplemented};
+
+    #[derive(Debug)]
+    struct ParseError(std::num::ParseIntError);
+
+    impl Error for ParseError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    impl Display for ParseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("could not parse the input")
+        }
+    }
+
+    #[test]
+    fn converts_options() {
+        assert_eq!(Some(42).into_outcome(), Outcome::Answer("42".into()));
+        assert_eq!(None::<u32>.into_outcome(), Outcome::Unsolved);
+        assert_eq!(
+            Some("#.\n.#").into_outcome(),
+            Outcome::MultiLine("#.\n.#".into())
+        );
+    }
+
+    #[test]
+    fn converts_results() {
+        assert_eq!(
+            Ok::<_, String>(42).into_outcome(),
+            Outcome::Answer("42".into())
+        );
+        assert_eq!(
+            Err::<u32, _>(Unimplemented).into_outcome(),
+            Outcome::Unimplemented
+        );
+        assert_eq!(
+            Err::<u32, _>("no path found").into_outcome(),
+            Outcome::Failed(vec!["no path found".into()])
+        );
+
+        let error = ParseError("x".parse::<u32>().unwrap_err());
+        assert_eq!(
+            Err::<u32, _>(error).into_outcome(),
+            Outcome::Failed(vec![
+                "could not parse the input".into(),
+                "invalid digit found in string".into()
+            ])
+        );
+    }
+
+    #[test]
+    fn converts_markers() {
+        assert_eq!(Unimplemented.into_outcome(), Outcome::Unimplemented);
+        assert_eq!(
+            MultiLine::new("#\n").into_outcome(),
+            Outcome::MultiLine("#".into())
+        );
+    }
+
+    #[test]
+    fn trims_multi_line_answers() {
+        let answer = MultiLine::new("#..# \n#### \n");
+        assert_eq!(answer.to_string(), "#..#\n####");
+    }
+
+    #[test]
+    fn reads_panic_messages() {
+        let payload = panic::catch_unwind(|| panic!("index {} out of bounds", 3)).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "index 3 out of bounds");
+    }
+}
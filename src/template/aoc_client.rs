@@ -0,0 +1,446 @@
+/// Native client for the Advent of Code website.
+/// Downloads inputs and puzzle descriptions and submits answers, authenticated with a session cookie.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::markdown::{articles, html_to_markdown, puzzle_to_markdown};
+use crate::template::{year_dir, Day, PuzzleId, Year};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_ENV_VAR: &str = "ADVENT_OF_CODE_SESSION";
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    Http(u16),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set {SESSION_ENV_VAR} or create \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::Http(400) => {
+                write!(f, "server responded with 400, the session cookie might have expired.")
+            }
+            AocClientError::Http(404) => write!(
+                f,
+                "server responded with 404, the puzzle might not be unlocked yet."
+            ),
+            AocClientError::Http(status) => write!(f, "server responded with {status}."),
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => AocClientError::Http(status),
+            ureq::Error::Transport(t) => AocClientError::Transport(t.to_string()),
+        }
+    }
+}
+
+/// Whether a wrong answer was too high or too low, if the server said so.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnswerHint {
+    TooHigh,
+    TooLow,
+}
+
+/// The server's verdict on a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect {
+        hint: Option<AnswerHint>,
+        cooldown: Option<Duration>,
+    },
+    /// An answer was submitted too recently, the duration is the remaining wait time.
+    RateLimited(Option<Duration>),
+    /// The part was already solved or is not unlocked yet.
+    WrongLevel,
+    /// A response that could not be interpreted, contains the message as markdown.
+    Unrecognized(String),
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionOutcome::Correct => write!(f, "That's the right answer! ⭐️"),
+            SubmissionOutcome::Incorrect { hint, cooldown } => {
+                write!(f, "That's not the right answer")?;
+                match hint {
+                    Some(AnswerHint::TooHigh) => write!(f, ", your answer is too high")?,
+                    Some(AnswerHint::TooLow) => write!(f, ", your answer is too low")?,
+                    None => {}
+                }
+                match cooldown {
+                    Some(d) => write!(f, ". Please wait {}s before trying again.", d.as_secs()),
+                    None => write!(f, "."),
+                }
+            }
+            SubmissionOutcome::RateLimited(Some(d)) => write!(
+                f,
+                "You gave an answer too recently, {}s left to wait.",
+                d.as_secs()
+            ),
+            SubmissionOutcome::RateLimited(None) => write!(f, "You gave an answer too recently."),
+            SubmissionOutcome::WrongLevel => write!(
+                f,
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            SubmissionOutcome::Unrecognized(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    year: Year,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        AocClient {
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build(),
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            year,
+        }
+    }
+
+    /// Creates a client for `year` from the environment.
+    /// The base url can be overridden with `AOC_BASE_URL`, e.g. to point to a mock server.
+    pub fn from_env(year: Year) -> Result<Self, AocClientError> {
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(AocClient::new(&base_url, &session, year))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let res = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?;
+        Ok(res.into_string()?)
+    }
+
+    /// Fetches the puzzle input of a day.
+    pub fn input(&self, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Fetches the puzzle description of a day as markdown.
+    pub fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let html = self.get(&self.day_url(day))?;
+        Ok(puzzle_to_markdown(&html))
+    }
+
+    /// Submits an answer for one part of a day.
+    pub fn submit(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, AocClientError> {
+        let res = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+        Ok(parse_submission(&res.into_string()?))
+    }
+}
+
+/// Interprets the response page of a submission.
+pub fn parse_submission(html: &str) -> SubmissionOutcome {
+    let message = articles(html)
+        .first()
+        .map_or_else(|| html_to_markdown(html), |a| html_to_markdown(a));
+
+    if message.contains("That's the right answer") {
+        SubmissionOutcome::Correct
+    } else if message.contains("That's not the right answer") {
+        let hint = if message.contains("too high") {
+            Some(AnswerHint::TooHigh)
+        } else if message.contains("too low") {
+            Some(AnswerHint::TooLow)
+        } else {
+            None
+        };
+        SubmissionOutcome::Incorrect {
+            hint,
+            cooldown: parse_wait(&message),
+        }
+    } else if message.contains("You gave an answer too recently") {
+        SubmissionOutcome::RateLimited(parse_wait(&message))
+    } else if message.contains("You don't seem to be solving the right level") {
+        SubmissionOutcome::WrongLevel
+    } else {
+        SubmissionOutcome::Unrecognized(message)
+    }
+}
+
+/// Parses wait times such as "You have 4m 32s left to wait" or "please wait one minute before trying again".
+fn parse_wait(message: &str) -> Option<Duration> {
+    if let Some(start) = message.find("You have ") {
+        let rest = &message[start + "You have ".len()..];
+        let end = rest.find(" left to wait")?;
+        let secs = rest[..end]
+            .split_whitespace()
+            .map(|token| {
+                let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+                let value: u64 = value.parse().ok()?;
+                match unit {
+                    "h" => Some(value * 3600),
+                    "m" => Some(value * 60),
+                    "s" => Some(value),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()?;
+        return Some(Duration::from_secs(secs));
+    }
+
+    let start = message.find("please wait ")? + "please wait ".len();
+    let mut words = message[start..].split_whitespace();
+    let amount = match words.next()? {
+        "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+        "eight" => 8,
+        "nine" => 9,
+        "ten" => 10,
+        n => n.parse().ok()?,
+    };
+    let unit = words.next()?;
+    if unit.starts_with("minute") {
+        Some(Duration::from_secs(amount * 60))
+    } else if unit.starts_with("second") {
+        Some(Duration::from_secs(amount))
+    } else {
+        None
+    }
+}
+
+/// Reads the session cookie from `ADVENT_OF_CODE_SESSION` or `~/.adventofcode.session`.
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var(SESSION_ENV_VAR) {
+        if !session.trim().is_empty() {
+            return Some(session);
+        }
+    }
+
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    let session = fs::read_to_string(PathBuf::from(home).join(SESSION_FILE_NAME)).ok()?;
+    (!session.trim().is_empty()).then_some(session)
+}
+
+fn get_input_path(puzzle: PuzzleId) -> PathBuf {
+    year_dir(puzzle.year)
+        .join("inputs")
+        .join(format!("{}.txt", puzzle.day))
+}
+
+fn get_puzzle_path(puzzle: PuzzleId) -> PathBuf {
+    year_dir(puzzle.year)
+        .join("puzzles")
+        .join(format!("{}.md", puzzle.day))
+}
+
+/// Writes `contents` to `path`, creating missing parent directories.
+fn write_file(path: &Path, contents: &str) -> Result<(), AocClientError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    Ok(fs::write(path, contents)?)
+}
+
+/// Prints the puzzle description of a day, downloading it first.
+pub fn read(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(puzzle.year)?;
+    let description = client.puzzle(puzzle.day)?;
+    write_file(&get_puzzle_path(puzzle), &description)?;
+    println!("{description}");
+    Ok(())
+}
+
+/// Downloads the input and the puzzle description of a day.
+pub fn download(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(puzzle.year)?;
+    let input_path = get_input_path(puzzle);
+    let puzzle_path = get_puzzle_path(puzzle);
+
+    write_file(&input_path, &client.input(puzzle.day)?)?;
+    write_file(&puzzle_path, &client.puzzle(puzzle.day)?)?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+/// Submits an answer for one part of a day.
+pub fn submit(
+    puzzle: PuzzleId,
+    part: u8,
+    result: &str,
+) -> Result<SubmissionOutcome, AocClientError> {
+    AocClient::from_env(puzzle.year)?.submit(puzzle.day, part, result)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_submission, AnswerHint, AocClient, SubmissionOutcome};
+    use crate::{day, year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    /// Serves a single request with `body` and returns the raw request.
+    fn mock_server(body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length: ") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn fetches_input_with_session() {
+        let (url, handle) = mock_server("1 2\n3 4\n");
+        let client = AocClient::new(&url, "secret\n", year!(2024));
+        assert_eq!(client.input(day!(1)).unwrap(), "1 2\n3 4\n");
+
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input "));
+        assert!(request.contains("session=secret"));
+    }
+
+    #[test]
+    fn fetches_puzzle_as_markdown() {
+        let (url, handle) = mock_server(
+            "<main><article class=\"day-desc\"><h2>--- Day 5: Test ---</h2><p>Hello</p></article></main>",
+        );
+        let client = AocClient::new(&url, "secret", year!(2024));
+        assert_eq!(
+            client.puzzle(day!(5)).unwrap(),
+            "## --- Day 5: Test ---\n\nHello"
+        );
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, handle) =
+            mock_server("<main><article><p>That's the right answer!</p></article></main>");
+        let client = AocClient::new(&url, "secret", year!(2024));
+        assert_eq!(
+            client.submit(day!(3), 2, "42").unwrap(),
+            SubmissionOutcome::Correct
+        );
+
+        let request = handle.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/3/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn parses_wrong_answers() {
+        let html = "<main><article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data; please wait one minute before trying again. [<a href=\"/2024/day/1\">Return to Day 1</a>]</p></article></main>";
+        assert_eq!(
+            parse_submission(html),
+            SubmissionOutcome::Incorrect {
+                hint: Some(AnswerHint::TooHigh),
+                cooldown: Some(Duration::from_secs(60)),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_rate_limits() {
+        let html = "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait.</p></article></main>";
+        assert_eq!(
+            parse_submission(html),
+            SubmissionOutcome::RateLimited(Some(Duration::from_secs(272)))
+        );
+    }
+
+    #[test]
+    fn parses_wrong_level() {
+        let html = "<main><article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article></main>";
+        assert_eq!(parse_submission(html), SubmissionOutcome::WrongLevel);
+    }
+}
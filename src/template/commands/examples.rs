@@ -0,0 +1,219 @@
+use std::{fs, path::Path, process};
+
+use crate::template::puzzle::{
+    answer_example, example_blocks, expected_answer, parts, read_puzzle,
+};
+use crate::template::{get_path_for_bin, year_dir, PuzzleId};
+
+pub fn handle(puzzle: PuzzleId, overwrite: bool) {
+    let Ok(description) = read_puzzle(puzzle) else {
+        eprintln!(
+            "Could not read the puzzle of day {puzzle}. Try running `cargo download {puzzle}` first."
+        );
+        process::exit(1);
+    };
+
+    let blocks = example_blocks(&description);
+
+    if blocks.is_empty() {
+        eprintln!("Could not find any example in the puzzle of day {puzzle}.");
+        process::exit(1);
+    }
+
+    let day = puzzle.day;
+    let examples_dir = year_dir(puzzle.year).join("examples");
+
+    for (i, block) in blocks.iter().enumerate() {
+        let path = if blocks.len() == 1 {
+            examples_dir.join(format!("{day}.txt"))
+        } else {
+            examples_dir.join(format!("{day}-{}.txt", i + 1))
+        };
+
+        if !overwrite && fs::read_to_string(&path).is_ok_and(|s| !s.trim().is_empty()) {
+            println!(
+                "Skipped \"{}\", it already has content. Use --overwrite to replace it.",
+                path.display()
+            );
+            continue;
+        }
+
+        match fs::create_dir_all(&examples_dir).and_then(|()| fs::write(&path, block)) {
+            Ok(()) => println!("Created example file \"{}\"", path.display()),
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let (part_one, part_two) = parts(&description);
+    let answers = [
+        expected_answer(part_one),
+        part_two.and_then(expected_answer),
+    ];
+
+    for (i, answer) in answers.iter().enumerate() {
+        match answer {
+            Some(answer) => println!("Expected example answer for part {}: {answer}", i + 1),
+            None => println!("Could not find an example answer for part {}.", i + 1),
+        }
+    }
+
+    // with several example files, each answer is checked against the last example before it.
+    let examples = if blocks.len() == 1 {
+        [Some(Example::Single); 2]
+    } else {
+        [
+            (0, part_one, &answers[0]),
+            (part_one.len(), part_two.unwrap_or_default(), &answers[1]),
+        ]
+        .map(|(offset, part, answer)| {
+            let answer = answer.as_ref()?;
+            let pos = offset + part.rfind(&format!("`*{answer}*`"))?;
+            Some(Example::File(answer_example(&description[..pos], &blocks)?))
+        })
+    };
+
+    let module_path = get_path_for_bin(puzzle);
+    if Path::new(&module_path).exists() {
+        let source = fs::read_to_string(&module_path).unwrap();
+        let updated = fill_expected_answers(&source, &answers, &examples);
+        if updated != source {
+            fs::write(&module_path, updated).unwrap();
+            println!("Filled expected answers in \"{module_path}\"");
+        }
+    }
+
+    if blocks.len() > 1 {
+        println!("---");
+        println!(
+            "🎄 Found {} candidate examples. Check which ones your tests need and use `read_file_part()` for them.",
+            blocks.len()
+        );
+    }
+}
+
+/// The example file a scaffolded test reads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Example {
+    /// `NN.txt`, read with `read_file()`.
+    Single,
+    /// `NN-k.txt`, read with `read_file_part()`.
+    File(u8),
+}
+
+/// Replaces the placeholder assertions of the scaffolded tests with the expected answers.
+/// Answers without a known example file are left out, so tests never read a file that was not written.
+fn fill_expected_answers(
+    source: &str,
+    answers: &[Option<String>; 2],
+    examples: &[Option<Example>; 2],
+) -> String {
+    let mut source = source.to_string();
+
+    for ((test_name, answer), example) in ["fn test_part_one()", "fn test_part_two()"]
+        .iter()
+        .zip(answers)
+        .zip(examples)
+    {
+        let (Some(answer), Some(example)) = (answer, example) else {
+            continue;
+        };
+
+        let Some(test_start) = source.find(test_name) else {
+            continue;
+        };
+
+        let placeholder = "assert_eq!(result, None);";
+        let Some(offset) = source[test_start..].find(placeholder) else {
+            continue;
+        };
+
+        // only touch the placeholder if it belongs to this test.
+        let pos = test_start + offset;
+        if source[test_start..pos].contains("#[test]") {
+            continue;
+        }
+
+        let value = if answer.parse::<i64>().is_ok() {
+            answer.clone()
+        } else {
+            format!("{answer:?}.to_string()")
+        };
+
+        source.replace_range(
+            pos..pos + placeholder.len(),
+            &format!("assert_eq!(result, Some({value}));"),
+        );
+
+        if let Example::File(file) = example {
+            let read_file = "read_file(\"examples\", PUZZLE)";
+            if let Some(offset) = source[test_start..pos].find(read_file) {
+                let start = test_start + offset;
+                source.replace_range(
+                    start..start + read_file.len(),
+                    &format!("read_file_part(\"examples\", PUZZLE, {file})"),
+                );
+            }
+        }
+    }
+
+    source
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fill_expected_answers, Example};
+
+    const SOURCE: &str = "#[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file(\"examples\", PUZZLE));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file(\"examples\", PUZZLE));
+        assert_eq!(result, None);
+    }";
+
+    #[test]
+    fn fills_both_parts() {
+        let res = fill_expected_answers(
+            SOURCE,
+            &[Some("11".into()), Some("31".into())],
+            &[Some(Example::Single); 2],
+        );
+        assert!(res.contains("assert_eq!(result, Some(11));"));
+        assert!(res.contains("assert_eq!(result, Some(31));"));
+    }
+
+    #[test]
+    fn leaves_missing_answers_alone() {
+        let res = fill_expected_answers(
+            SOURCE,
+            &[None, Some("abc".into())],
+            &[Some(Example::Single); 2],
+        );
+        assert_eq!(res.matches("assert_eq!(result, None);").count(), 1);
+        assert!(res.contains("assert_eq!(result, Some(\"abc\".to_string()));"));
+    }
+
+    #[test]
+    fn reads_the_example_file_of_each_answer() {
+        let res = fill_expected_answers(
+            SOURCE,
+            &[Some("11".into()), Some("31".into())],
+            &[Some(Example::File(1)), None],
+        );
+        assert!(res.contains(
+            "part_one(&advent_of_code::template::read_file_part(\"examples\", PUZZLE, 1));"
+        ));
+        assert!(res.contains("assert_eq!(result, Some(11));"));
+        assert!(
+            res.contains("part_two(&advent_of_code::template::read_file(\"examples\", PUZZLE));")
+        );
+        assert!(!res.contains("Some(31)"));
+    }
+}
@@ -1,12 +1,18 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::profiler::FLAME_ARG;
+use crate::template::PuzzleId;
+
+pub fn handle(puzzle: PuzzleId, debug: bool, dhat: bool, part: Option<u8>) {
+    if let Some(part) = part {
+        handle_part(puzzle, debug, part);
+        return;
+    }
 
-pub fn handle(day: Day, debug: bool, dhat: bool) {
     let mut cmd_args = vec![
         "flamegraph".to_string(),
         "--bin".to_string(),
-        day.to_string(),
+        puzzle.bin_name(),
     ];
 
     if dhat {
@@ -35,3 +41,37 @@ pub fn handle(day: Day, debug: bool, dhat: bool) {
 
     cmd.wait().unwrap();
 }
+
+/// Profiles a single part with the built-in sampler of the `profile` feature.
+fn handle_part(puzzle: PuzzleId, debug: bool, part: u8) {
+    if !cfg!(target_os = "linux") {
+        eprintln!("Profiling a single part is only supported on Linux. Run `cargo flame {puzzle}` without `--part` to use cargo-flamegraph.");
+        process::exit(1);
+    }
+
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        puzzle.bin_name(),
+        "--features".to_string(),
+        "profile".to_string(),
+    ];
+
+    if !debug {
+        cmd_args.push("--release".to_string());
+    }
+
+    cmd_args.extend(["--".to_string(), FLAME_ARG.to_string(), part.to_string()]);
+
+    let status = Command::new("cargo")
+        .args(&cmd_args)
+        .env("CARGO_PROFILE_RELEASE_DEBUG", "true")
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        process::exit(1);
+    }
+}
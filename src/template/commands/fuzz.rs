@@ -0,0 +1,36 @@
+use std::process;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::template::fuzz::{fuzz, Options};
+use crate::template::PuzzleId;
+
+const DEFAULT_RUNS: usize = 100;
+const DEFAULT_MAX_SIZE: usize = 50;
+const DEFAULT_TIMEOUT_MS: u64 = 5000;
+
+pub fn handle(
+    puzzle: PuzzleId,
+    runs: Option<usize>,
+    max_size: Option<usize>,
+    seed: Option<u64>,
+    timeout_ms: Option<u64>,
+) {
+    // without a seed, every invocation tries new cases. The seed is printed to reproduce them.
+    #[allow(clippy::cast_possible_truncation)]
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64 % 1_000_000)
+    });
+
+    let options = Options {
+        runs: runs.unwrap_or(DEFAULT_RUNS),
+        max_size: max_size.unwrap_or(DEFAULT_MAX_SIZE),
+        seed,
+        timeout: Duration::from_millis(timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS)),
+    };
+
+    if !fuzz(puzzle, options) {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,18 @@
+use std::path::Path;
+use std::{fs, process};
+
+use crate::template::leaderboard::{print, Leaderboard};
+
+pub fn handle(path: &Path) {
+    let leaderboard = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(Leaderboard::try_from);
+
+    match leaderboard {
+        Ok(leaderboard) => print(&leaderboard),
+        Err(e) => {
+            eprintln!("failed to read leaderboard \"{}\": {e}", path.display());
+            process::exit(1);
+        }
+    }
+}
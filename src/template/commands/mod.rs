@@ -1,6 +1,9 @@
 pub mod all;
 pub mod download;
+pub mod examples;
 pub mod flame;
+pub mod fuzz;
+pub mod leaderboard;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
@@ -1,15 +1,103 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::puzzle::{read_puzzle, title};
+use crate::template::{get_path_for_bin, year_dir, PuzzleId};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+const TEMPLATES_DIR: &str = "templates";
+
+/// Loads a template from `templates/<name>.txt`, or the built-in `src/template.txt` if no name is given.
+fn load_template(name: Option<&str>) -> Result<String, String> {
+    let Some(name) = name else {
+        return Ok(MODULE_TEMPLATE.into());
+    };
+
+    let path = Path::new(TEMPLATES_DIR).join(format!("{name}.txt"));
+    fs::read_to_string(&path).map_err(|_| {
+        let available: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .filter_map(|e| {
+                        let file_name = e.file_name().into_string().ok()?;
+                        file_name.strip_suffix(".txt").map(String::from)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        format!(
+            "could not read template \"{}\". Available templates: {}",
+            path.display(),
+            if available.is_empty() {
+                "none".into()
+            } else {
+                available.join(", ")
+            }
+        )
+    })
+}
+
+/// Builds the placeholder values for a day. User-defined variables take precedence over built-in ones.
+fn template_vars(puzzle: PuzzleId, user_vars: &[(String, String)]) -> Vec<(String, String)> {
+    let day = puzzle.day;
+    let title = read_puzzle(puzzle)
+        .ok()
+        .and_then(|puzzle| title(&puzzle))
+        .unwrap_or_else(|| format!("Day {}", day.into_inner()));
+
+    let mut vars: Vec<(String, String)> = user_vars
+        .iter()
+        .map(|(k, v)| (k.to_uppercase(), v.clone()))
+        .collect();
+
+    vars.extend([
+        ("DAY_NUMBER".into(), day.into_inner().to_string()),
+        ("DAY_PADDED".into(), day.to_string()),
+        ("YEAR".into(), puzzle.year.to_string()),
+        ("TITLE".into(), title),
+    ]);
+
+    vars
+}
+
+/// Replaces every `%KEY%` placeholder. The first occurrence of a key in `vars` wins.
+/// The template is read in a single pass, so placeholders inside of values are kept as they are.
+fn render(template: &str, vars: &[(String, String)]) -> String {
+    let mut res = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('%') {
+        res.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        let value = rest.find('%').and_then(|end| {
+            let (_, value) = vars.iter().find(|(key, _)| *key == rest[..end])?;
+            Some((end, value))
+        });
+
+        match value {
+            Some((end, value)) => {
+                res.push_str(value);
+                rest = &rest[end + 1..];
+            }
+            // the closing `%` may open the next placeholder.
+            None => res.push('%'),
+        }
+    }
+
+    res.push_str(rest);
+    res
+}
+
+fn safe_create_file(path: impl AsRef<Path>, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,7 +107,7 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: impl AsRef<Path>) -> Result<File, std::io::Error> {
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +115,32 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(
+    puzzle: PuzzleId,
+    overwrite: bool,
+    template: Option<&str>,
+    vars: &[(String, String)],
+) {
+    let template = match load_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            process::exit(1);
+        }
+    };
+
+    let day = puzzle.day;
+    let data_dir = year_dir(puzzle.year);
+    let input_path = data_dir.join("inputs").join(format!("{day}.txt"));
+    let example_path = data_dir.join("examples").join(format!("{day}.txt"));
+    let module_path = get_path_for_bin(puzzle);
+
+    for dir in ["inputs", "examples", "puzzles"] {
+        if let Err(e) = fs::create_dir_all(data_dir.join(dir)) {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +150,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(render(&template, &template_vars(puzzle, vars)).as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -56,7 +162,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -66,7 +172,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file \"{}\"", example_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -75,5 +181,47 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!("🎄 Type `cargo solve {puzzle}` to run your solution.");
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+
+    #[test]
+    fn renders_placeholders() {
+        let vars = vec![
+            ("AUTHOR".to_string(), "alice".to_string()),
+            ("DAY_NUMBER".to_string(), "5".to_string()),
+            ("DAY_PADDED".to_string(), "05".to_string()),
+        ];
+        assert_eq!(
+            render(
+                "solution!(%DAY_NUMBER%); // %DAY_PADDED% by %AUTHOR% %UNKNOWN%",
+                &vars
+            ),
+            "solution!(5); // 05 by alice %UNKNOWN%"
+        );
+    }
+
+    #[test]
+    fn user_vars_take_precedence() {
+        let vars = vec![
+            ("TITLE".to_string(), "mine".to_string()),
+            ("TITLE".to_string(), "builtin".to_string()),
+        ];
+        assert_eq!(render("%TITLE%", &vars), "mine");
+    }
+
+    #[test]
+    fn does_not_expand_placeholders_in_values() {
+        let vars = vec![
+            ("TITLE".to_string(), "100% %DAY_NUMBER%".to_string()),
+            ("DAY_NUMBER".to_string(), "5".to_string()),
+        ];
+        assert_eq!(
+            render("50% of %TITLE%: %DAY_NUMBER%%", &vars),
+            "50% of 100% %DAY_NUMBER%: 5%"
+        );
+    }
 }
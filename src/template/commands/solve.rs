@@ -1,9 +1,21 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::run_inputs::{has_problems, run_inputs};
+use crate::template::watch::watch;
+use crate::template::PuzzleId;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    alloc: bool,
+    submit_part: Option<u8>,
+    input: Option<String>,
+    profile: Option<String>,
+    params: &[String],
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -16,6 +28,10 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if alloc {
+        cmd_args.extend(["--features".to_string(), "alloc-stats".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,6 +39,8 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    cmd_args.extend(binary_args(input, profile, params));
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -32,3 +50,39 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+pub fn handle_all_inputs(puzzle: PuzzleId, release: bool) {
+    let outcomes = run_inputs(puzzle, release);
+    if outcomes.is_empty() || has_problems(&outcomes) {
+        process::exit(1);
+    }
+}
+
+pub fn handle_watch(
+    puzzle: PuzzleId,
+    release: bool,
+    input: Option<String>,
+    profile: Option<String>,
+    params: &[String],
+) {
+    watch(puzzle, release, &binary_args(input, profile, params));
+}
+
+/// Arguments of a solution binary that select its input and parameters.
+fn binary_args(input: Option<String>, profile: Option<String>, params: &[String]) -> Vec<String> {
+    let mut args = vec![];
+
+    if let Some(input) = input {
+        args.extend(["--input".to_string(), input]);
+    }
+
+    if let Some(profile) = profile {
+        args.extend(["--profile".to_string(), profile]);
+    }
+
+    for param in params {
+        args.extend(["--param".to_string(), param.clone()]);
+    }
+
+    args
+}
@@ -1,19 +1,66 @@
 use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{env, fs, process};
 
+use crate::template::compare::{compare, report};
+use crate::template::counters::Metric;
+use crate::template::history::{print_chart, History, Record};
+use crate::template::report::Report;
 use crate::template::run_multi::run_multi;
+use crate::template::scale::{scale, Options};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, readme_stars, Day, PuzzleId, Year};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+const BUDGET_ENV: &str = "AOC_BUDGET_MS";
+
+const DEFAULT_SCALE_MAX_SIZE: usize = 4096;
+const DEFAULT_SCALE_TIMEOUT_MS: u64 = 30_000;
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare_threshold: Option<f64>,
+    count_allocs: bool,
+    metric: Metric,
+    html: Option<PathBuf>,
+) {
+    // read stored files up front, so a damaged file is reported before spending time on benchmarks.
+    let stored_timings = match Timings::read_from_file(year) {
+        Ok(timings) => timings,
+        Err(e) => {
+            eprintln!("Could not read the stored timings, fix or remove them first: {e}");
+            process::exit(1);
+        }
+    };
+
+    let history = if store {
+        match History::read_from_file(year) {
+            Ok(history) => Some(history),
+            Err(e) => {
+                eprintln!(
+                    "Could not read the timing history, fix or remove it to store timings: {e}"
+                );
+                process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                all_days(year).collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, re-run every day that has a stored baseline.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                all_days(year)
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,14 +68,34 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(
+        year,
+        &days_to_run,
+        true,
+        true,
+        count_allocs,
+        metric == Metric::Instructions,
+    )
+    .unwrap();
+
+    let regressions = compare_threshold.map_or(0, |threshold| {
+        report(&compare(&stored_timings, &timings, threshold), threshold)
+    });
 
-    if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+    let record = Record::new(timings.clone(), true);
+    let merged_timings = stored_timings.merge(&timings);
+    let report = Report::new(year, merged_timings.clone())
+        .with_budget(read_budget())
+        .with_environment(&record);
+
+    if let Some(mut history) = history {
+        history.push(record);
+        history.store_file(year).unwrap();
+
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(&report) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -36,5 +103,49 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
                 eprintln!("Failed to store updated benchmarks.");
             }
         }
+
+        if readme_stars::update(year).is_err() {
+            eprintln!("Failed to update the star table.");
+        }
+    }
+
+    if let Some(path) = html {
+        match fs::write(&path, report.html()) {
+            Ok(()) => println!("Wrote benchmark report to \"{}\".", path.display()),
+            Err(e) => eprintln!("Failed to write benchmark report: {e}"),
+        }
+    }
+
+    if regressions > 0 {
+        process::exit(1);
     }
 }
+
+/// Reads the time budget of a single day from `AOC_BUDGET_MS`, as nanoseconds.
+fn read_budget() -> Option<f64> {
+    env::var(BUDGET_ENV)
+        .ok()
+        .and_then(|ms| ms.trim().parse::<f64>().ok())
+        .filter(|ms| *ms > 0_f64)
+        .map(|ms| ms * 1_000_000_f64)
+}
+
+pub fn handle_history(puzzle: PuzzleId) {
+    match History::read_from_file(puzzle.year) {
+        Ok(history) => print_chart(&history, puzzle),
+        Err(e) => {
+            eprintln!("Could not read the timing history: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+pub fn handle_scale(puzzle: PuzzleId, max_size: Option<usize>, timeout_ms: Option<u64>) {
+    scale(
+        puzzle,
+        Options {
+            max_size: max_size.unwrap_or(DEFAULT_SCALE_MAX_SIZE),
+            timeout: Duration::from_millis(timeout_ms.unwrap_or(DEFAULT_SCALE_TIMEOUT_MS)),
+        },
+    );
+}
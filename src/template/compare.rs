@@ -0,0 +1,177 @@
+/// Module that compares fresh benchmark timings against a stored baseline.
+use crate::template::timings::{format_nanos, Timing, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Default slowdown (in percent) above which a part counts as a regression.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Differences below this absolute value are treated as measurement noise.
+/// Sub-microsecond timings fluctuate a lot between runs and would otherwise be flagged constantly.
+const NOISE_FLOOR_NANOS: f64 = 1_000.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Faster,
+    Slower,
+    Regression,
+    Unchanged,
+}
+
+/// Represents the comparison of a single part of a single day.
+#[derive(Clone, Debug)]
+pub struct PartComparison {
+    pub day: Day,
+    pub part: u8,
+    pub baseline_nanos: f64,
+    pub current_nanos: f64,
+    pub change_pct: f64,
+    pub verdict: Verdict,
+}
+
+/// Compare `current` against `baseline`, skipping parts that are missing from either side.
+pub fn compare(baseline: &Timings, current: &Timings, threshold: f64) -> Vec<PartComparison> {
+    current
+        .data
+        .iter()
+        .filter_map(|timing| {
+            let stored = baseline.data.iter().find(|t| t.day == timing.day)?;
+            Some((stored, timing))
+        })
+        .flat_map(|(stored, timing)| {
+            [1, 2]
+                .into_iter()
+                .filter_map(move |part| compare_part(stored, timing, part, threshold))
+        })
+        .collect()
+}
+
+fn compare_part(
+    baseline: &Timing,
+    current: &Timing,
+    part: u8,
+    threshold: f64,
+) -> Option<PartComparison> {
+    let (baseline_nanos, current_nanos) = match part {
+        1 => (baseline.part_1?, current.part_1?),
+        _ => (baseline.part_2?, current.part_2?),
+    };
+
+    if baseline_nanos <= 0_f64 {
+        return None;
+    }
+
+    let delta = current_nanos - baseline_nanos;
+    let change_pct = delta / baseline_nanos * 100_f64;
+
+    let verdict = if delta.abs() < NOISE_FLOOR_NANOS {
+        Verdict::Unchanged
+    } else if change_pct > threshold {
+        Verdict::Regression
+    } else if change_pct > 0_f64 {
+        Verdict::Slower
+    } else if change_pct < 0_f64 {
+        Verdict::Faster
+    } else {
+        Verdict::Unchanged
+    };
+
+    Some(PartComparison {
+        day: current.day,
+        part,
+        baseline_nanos,
+        current_nanos,
+        change_pct,
+        verdict,
+    })
+}
+
+/// Print a comparison report and return the number of regressions.
+pub fn report(comparisons: &[PartComparison], threshold: f64) -> usize {
+    println!(
+        "\n{ANSI_BOLD}Comparison against stored timings{ANSI_RESET} (threshold: {threshold:.1}%)"
+    );
+
+    if comparisons.is_empty() {
+        println!("No stored timings to compare against.");
+        return 0;
+    }
+
+    for c in comparisons {
+        let label = match c.verdict {
+            Verdict::Faster => "faster",
+            Verdict::Slower => "slower",
+            Verdict::Regression => "REGRESSION",
+            Verdict::Unchanged => "within noise",
+        };
+
+        println!(
+            "Day {} Part {}: {} -> {} ({ANSI_ITALIC}{:+.1}%{ANSI_RESET}) {label}",
+            c.day,
+            c.part,
+            format_nanos(c.baseline_nanos),
+            format_nanos(c.current_nanos),
+            c.change_pct
+        );
+    }
+
+    let regressions = comparisons
+        .iter()
+        .filter(|c| c.verdict == Verdict::Regression)
+        .count();
+
+    if regressions > 0 {
+        println!("{ANSI_BOLD}{regressions} regression(s) above {threshold:.1}%.{ANSI_RESET}");
+    }
+
+    regressions
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, Verdict};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn timings(part_1: f64, part_2: Option<f64>) -> Timings {
+        Timings {
+            data: vec![Timing {
+                part_1: Some(part_1),
+                part_2,
+                ..Timing::new(day!(1))
+            }],
+        }
+    }
+
+    #[test]
+    fn detects_regressions() {
+        let res = compare(&timings(1e+7, None), &timings(1.2e+7, None), 10.0);
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].verdict, Verdict::Regression);
+        assert!((res[0].change_pct - 20.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn detects_speedups_and_small_slowdowns() {
+        let res = compare(
+            &timings(1e+7, Some(1e+7)),
+            &timings(5e+6, Some(1.05e+7)),
+            10.0,
+        );
+        assert_eq!(res[0].verdict, Verdict::Faster);
+        assert_eq!(res[1].verdict, Verdict::Slower);
+    }
+
+    #[test]
+    fn ignores_changes_below_noise_floor() {
+        let res = compare(&timings(100.0, None), &timings(300.0, None), 10.0);
+        assert_eq!(res[0].verdict, Verdict::Unchanged);
+    }
+
+    #[test]
+    fn skips_days_without_baseline() {
+        let res = compare(&Timings::default(), &timings(1e+6, Some(2e+6)), 10.0);
+        assert!(res.is_empty());
+    }
+}
@@ -0,0 +1,419 @@
+/// Instruction and cache-miss counts of a solution part, which are more stable across runs and machines than wall-clock time.
+/// With the `perf-counters` feature, the runner reads hardware counters via `perf_event_open` on Linux.
+/// Where those are not available, `cargo time` falls back to running the part under valgrind's cachegrind.
+use std::env;
+use std::fmt::Display;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+
+/// Passed to a solution binary to only run one of its parts, `0` runs none of them.
+/// Used to tell the counts of a part apart from reading the input when running under cachegrind.
+pub const ONLY_PART_ARG: &str = "--only-part";
+
+/// What `cargo time` measures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    Time,
+    Instructions,
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "time" => Ok(Metric::Time),
+            "instructions" => Ok(Metric::Instructions),
+            _ => Err(format!(
+                "unknown metric `{s}`, expected `time` or `instructions`."
+            )),
+        }
+    }
+}
+
+/// Reads the part to run from the `--only-part` argument of a solution binary.
+pub fn only_part() -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+    args.windows(2)
+        .find(|w| w[0] == ONLY_PART_ARG)
+        .and_then(|w| w[1].parse().ok())
+}
+
+/// Counts of a single execution of a solution part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CounterStats {
+    /// Retired instructions, in user space.
+    pub instructions: u64,
+    /// Misses of the last level cache. Not every machine exposes this counter.
+    pub cache_misses: Option<u64>,
+}
+
+impl Display for CounterStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{{} instructions", self.instructions)?;
+        if let Some(cache_misses) = self.cache_misses {
+            write!(f, ", {cache_misses} cache misses")?;
+        }
+        f.write_str("}")
+    }
+}
+
+impl CounterStats {
+    /// Parses the output of the [`Display`] implementation from a line of runner output.
+    pub fn parse(line: &str) -> Option<Self> {
+        let stats = line.rsplit('{').next()?.split('}').next()?;
+        if !stats.contains(" instructions") {
+            return None;
+        }
+
+        let mut instructions = None;
+        let mut cache_misses = None;
+        for stat in stats.split(", ") {
+            let (count, name) = stat.trim().split_once(' ')?;
+            match name {
+                "instructions" => instructions = count.parse().ok(),
+                "cache misses" => cache_misses = count.parse().ok(),
+                _ => return None,
+            }
+        }
+
+        Some(CounterStats {
+            instructions: instructions?,
+            cache_misses,
+        })
+    }
+
+    /// Counts of `self` minus the counts of `baseline`, e.g. to remove the cost of reading the input.
+    #[must_use]
+    pub fn saturating_sub(self, baseline: Self) -> Self {
+        CounterStats {
+            instructions: self.instructions.saturating_sub(baseline.instructions),
+            cache_misses: self
+                .cache_misses
+                .zip(baseline.cache_misses)
+                .map(|(count, baseline)| count.saturating_sub(baseline)),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns `true` if valgrind can be invoked.
+pub fn has_valgrind() -> bool {
+    Command::new("valgrind")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Counts a part of a solution binary under cachegrind.
+/// The binary runs twice, once with the part and once without any part, and the difference is returned.
+pub fn count_part(bin: &Path, part: u8) -> Option<CounterStats> {
+    let total = run_cachegrind(bin, &[ONLY_PART_ARG, &part.to_string()])?;
+    let baseline = run_cachegrind(bin, &[ONLY_PART_ARG, "0"])?;
+    Some(total.saturating_sub(baseline))
+}
+
+/// Runs `bin` with `args` under cachegrind and reads the counts of the whole execution.
+fn run_cachegrind(bin: &Path, args: &[&str]) -> Option<CounterStats> {
+    let output = Command::new("valgrind")
+        .args([
+            "--tool=cachegrind",
+            "--cache-sim=yes",
+            "--cachegrind-out-file=/dev/null",
+        ])
+        .arg(bin)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+
+    parse_cachegrind(&String::from_utf8_lossy(&output.stderr))
+}
+
+/// Reads the summary cachegrind prints to stderr, e.g. `==12== I refs: 1,234` and `==12== LL misses: 56 (...)`.
+pub fn parse_cachegrind(stderr: &str) -> Option<CounterStats> {
+    let count = |label: &str| {
+        stderr.lines().find_map(|line| {
+            let (_, rest) = line.split_once(label)?;
+            rest.split_whitespace()
+                .next()?
+                .replace(',', "")
+                .parse::<u64>()
+                .ok()
+        })
+    };
+
+    Some(CounterStats {
+        instructions: count("I refs:").or_else(|| count("I   refs:"))?,
+        cache_misses: count("LL misses:"),
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(feature = "perf-counters", target_os = "linux"))]
+pub use perf::Counters;
+
+/// Hardware counters, read with `perf_event_open`.
+#[cfg(all(feature = "perf-counters", target_os = "linux"))]
+mod perf {
+    use std::io;
+    use std::sync::OnceLock;
+
+    use super::CounterStats;
+
+    const PERF_TYPE_HARDWARE: u32 = 0;
+    const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+    const PERF_COUNT_HW_CACHE_MISSES: u64 = 3;
+
+    const FLAG_DISABLED: u64 = 1 << 0;
+    const FLAG_INHERIT: u64 = 1 << 1;
+    const FLAG_EXCLUDE_KERNEL: u64 = 1 << 5;
+    const FLAG_EXCLUDE_HV: u64 = 1 << 6;
+
+    // _IO('$', n)
+    const PERF_EVENT_IOC_ENABLE: u64 = 0x2400;
+    const PERF_EVENT_IOC_DISABLE: u64 = 0x2401;
+    const PERF_EVENT_IOC_RESET: u64 = 0x2403;
+
+    /// The first version of `struct perf_event_attr` (`PERF_ATTR_SIZE_VER0`), which every kernel accepts.
+    #[repr(C)]
+    #[derive(Default)]
+    struct PerfEventAttr {
+        type_: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    struct Counter(libc::c_int);
+
+    impl Counter {
+        fn open(config: u64) -> io::Result<Self> {
+            let attr = PerfEventAttr {
+                type_: PERF_TYPE_HARDWARE,
+                #[allow(clippy::cast_possible_truncation)]
+                size: std::mem::size_of::<PerfEventAttr>() as u32,
+                config,
+                // counting in user space only does not need elevated privileges.
+                // inherited counters also count threads spawned later on, e.g. the workers of rayon.
+                flags: FLAG_DISABLED | FLAG_INHERIT | FLAG_EXCLUDE_KERNEL | FLAG_EXCLUDE_HV,
+                ..Default::default()
+            };
+
+            // SAFETY: `attr` is a valid `perf_event_attr` of the size given in `attr.size`.
+            // pid 0 and cpu -1 count the calling thread and the threads it spawns on any cpu.
+            let fd = unsafe {
+                libc::syscall(
+                    libc::SYS_perf_event_open,
+                    &attr as *const PerfEventAttr,
+                    0,
+                    -1,
+                    -1,
+                    0,
+                )
+            };
+
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            #[allow(clippy::cast_possible_truncation)]
+            Ok(Counter(fd as libc::c_int))
+        }
+
+        fn ioctl(&self, request: u64) {
+            // SAFETY: `self.0` is an open perf event file descriptor and the request takes no argument.
+            unsafe {
+                libc::ioctl(self.0, request as _, 0);
+            }
+        }
+
+        fn read(&self) -> Option<u64> {
+            let mut count: u64 = 0;
+            // SAFETY: reads at most 8 bytes into `count`.
+            let read = unsafe {
+                libc::read(
+                    self.0,
+                    std::ptr::addr_of_mut!(count).cast(),
+                    std::mem::size_of::<u64>(),
+                )
+            };
+            (read == 8).then_some(count)
+        }
+    }
+
+    impl Drop for Counter {
+        fn drop(&mut self) {
+            // SAFETY: the file descriptor is owned by this counter.
+            unsafe {
+                libc::close(self.0);
+            }
+        }
+    }
+
+    /// Counts the instructions and cache misses between [`Counters::start`] and [`Counters::stop`].
+    /// The counters are opened once per process and inherited by every thread spawned afterwards,
+    /// so parts that use rayon are counted in full, even if its thread pool was started by an earlier part.
+    pub struct Counters {
+        instructions: Counter,
+        cache_misses: Option<Counter>,
+    }
+
+    static COUNTERS: OnceLock<Option<Counters>> = OnceLock::new();
+
+    impl Counters {
+        /// Opens the counters on first use, then resets and starts them.
+        /// Returns `None` if the machine does not expose them, the reason is printed once per process.
+        pub fn start() -> Option<&'static Self> {
+            let counters = COUNTERS.get_or_init(Counters::open).as_ref()?;
+
+            for counter in counters.all() {
+                counter.ioctl(PERF_EVENT_IOC_RESET);
+                counter.ioctl(PERF_EVENT_IOC_ENABLE);
+            }
+
+            Some(counters)
+        }
+
+        fn open() -> Option<Self> {
+            let instructions = match Counter::open(PERF_COUNT_HW_INSTRUCTIONS) {
+                Ok(counter) => counter,
+                Err(e) => {
+                    eprintln!("Warning: could not read performance counters: {e}.");
+                    return None;
+                }
+            };
+
+            Some(Counters {
+                instructions,
+                cache_misses: Counter::open(PERF_COUNT_HW_CACHE_MISSES).ok(),
+            })
+        }
+
+        /// Stops the counters and reads them.
+        pub fn stop(&self) -> Option<CounterStats> {
+            for counter in self.all() {
+                counter.ioctl(PERF_EVENT_IOC_DISABLE);
+            }
+
+            Some(CounterStats {
+                instructions: self.instructions.read()?,
+                cache_misses: self.cache_misses.as_ref().and_then(Counter::read),
+            })
+        }
+
+        fn all(&self) -> impl Iterator<Item = &Counter> {
+            std::iter::once(&self.instructions).chain(self.cache_misses.as_ref())
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_cachegrind, CounterStats};
+
+    #[test]
+    fn parses_runner_output() {
+        let stats = CounterStats {
+            instructions: 123_456,
+            cache_misses: Some(78),
+        };
+        let line =
+            format!("Part 1: 42 (1.2ms @ 812 samples) [3 allocs, 96 bytes, peak 64 bytes] {stats}");
+        assert_eq!(CounterStats::parse(&line), Some(stats));
+
+        let stats = CounterStats {
+            instructions: 9,
+            cache_misses: None,
+        };
+        assert_eq!(
+            CounterStats::parse(&format!("Part 2: 1 (1.0µs) {stats}")),
+            Some(stats)
+        );
+        assert_eq!(
+            CounterStats::parse("Part 1: 42 (1.2ms @ 812 samples)"),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_cachegrind_summary() {
+        let stderr = "==42== Cachegrind, a high-precision tracing profiler\n\
+            ==42== I refs:        1,234,567\n\
+            ==42== I1  misses:        1,000\n\
+            ==42== LL misses:         2,345  (  1,234 rd   +   1,111 wr)\n";
+        assert_eq!(
+            parse_cachegrind(stderr),
+            Some(CounterStats {
+                instructions: 1_234_567,
+                cache_misses: Some(2345)
+            })
+        );
+        assert_eq!(parse_cachegrind("==42== error"), None);
+    }
+
+    #[cfg(all(feature = "perf-counters", target_os = "linux"))]
+    #[test]
+    fn counts_rayon_workers() {
+        use rayon::prelude::*;
+
+        // skip on machines that do not expose the counters.
+        let Some(counters) = super::Counters::start() else {
+            return;
+        };
+        counters.stop();
+
+        // the pool is started after the counters were opened, like the global pool of a solution.
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        let work = || {
+            pool.install(|| {
+                (0..4_000_000_u64)
+                    .into_par_iter()
+                    .map(|n| n.wrapping_mul(n) % 7)
+                    .sum::<u64>()
+            })
+        };
+        std::hint::black_box(work());
+
+        // the calling thread only waits while the workers sum, so their instructions make up the count.
+        let counters = super::Counters::start().unwrap();
+        std::hint::black_box(work());
+        let stats = counters.stop().unwrap();
+        assert!(stats.instructions > 4_000_000, "{stats}");
+    }
+
+    #[test]
+    fn subtracts_baselines() {
+        let total = CounterStats {
+            instructions: 100,
+            cache_misses: Some(10),
+        };
+        let baseline = CounterStats {
+            instructions: 40,
+            cache_misses: None,
+        };
+        assert_eq!(
+            total.saturating_sub(baseline),
+            CounterStats {
+                instructions: 60,
+                cache_misses: None
+            }
+        );
+    }
+}
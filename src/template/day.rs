@@ -2,14 +2,20 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::template::Year;
+
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
 #[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+pub(super) const SERVER_UTC_OFFSET: i32 = -5;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
+/// A [`Day`] does not know its year, so it accepts every day of the longest event.
+/// Some events are shorter: use [`Year::contains`] to check a day against the range of a particular year,
+/// or [`PuzzleId::parse`](crate::template::PuzzleId::parse), which rejects days after [`Year::last_day`].
+///
 /// # Display
 /// This value displays as a two digit number.
 ///
@@ -23,7 +29,7 @@ pub struct Day(u8);
 
 impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
-    /// returns [`None`] otherwise.
+    /// returns [`None`] otherwise. The range does not depend on the year, see [`Year::contains`].
     pub fn new(day: u8) -> Option<Self> {
         if day == 0 || day > 25 {
             return None;
@@ -45,11 +51,12 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if the event of the current year is running, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        let year = Year::new(u16::try_from(today.year()).ok()?)?;
+        if today.month() == 12 && today.day() <= u32::from(year.last_day()) {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
@@ -100,20 +107,23 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// An iterator that yields every day of advent of `year`, from the 1st to its last day.
+pub fn all_days(year: Year) -> AllDays {
+    AllDays::new(year)
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent of a year, from the 1st to its last day.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(year: Year) -> Self {
+        Self {
+            current: 1,
+            last: year.last_day(),
+        }
     }
 }
 
@@ -121,10 +131,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and `Year::last_day` is never above 25.
         let day = Day(self.current);
         self.current += 1;
 
@@ -135,6 +145,7 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+/// Like [`Day::new`], it accepts any day from 1 to 25, regardless of the year.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
@@ -155,10 +166,11 @@ macro_rules! day {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{all_days, Day};
+    use crate::year;
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = all_days(year!(2024));
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +199,13 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_iterator_short_event() {
+        let days: Vec<Day> = all_days(year!(2025)).collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.last(), Some(&Day(12)));
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,156 @@
+/// Differential tests of a solution against its reference implementation, generated by the [`differential!`](crate::differential) macro.
+/// Both are run on every example file of the day and, if the day has a generator, on generated inputs.
+/// A difference on a generated input is shrunk to the smallest size that still differs, see [`shrink`].
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+
+use crate::template::answer::panic_message;
+use crate::template::generate::{case_size, shrink, Case, Generator};
+use crate::template::{read_path, year_dir, Outcome, PartResult, PuzzleId};
+
+/// Number of generated inputs every part is tested on.
+const CASES: usize = 25;
+
+/// Generated inputs grow from size 1 to this size. Reference implementations are slow, so it is kept small.
+const MAX_SIZE: usize = 10;
+
+/// Lists the example files of a day: `NN.txt` and `NN-<n>.txt`.
+pub fn example_files(puzzle: PuzzleId) -> Vec<PathBuf> {
+    let day = puzzle.day.to_string();
+    let mut files: Vec<PathBuf> = fs::read_dir(year_dir(puzzle.year).join("examples"))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|e| e.path())
+                .filter(|path| {
+                    path.file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .is_some_and(|stem| {
+                            stem == day || stem.strip_prefix(&format!("{day}-")).is_some()
+                        })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    files.sort_unstable();
+    files
+}
+
+/// Runs a part, turning a panic into its outcome.
+fn run<R: PartResult>(func: impl Fn(&str) -> R, input: &str) -> Outcome {
+    match panic::catch_unwind(AssertUnwindSafe(|| func(input))) {
+        Ok(result) => result.into_outcome(),
+        Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
+    }
+}
+
+/// Returns the outcomes of the solution and the reference if they differ.
+/// Inputs the reference does not answer, e.g. an example of the other part, are skipped.
+fn differs<R1: PartResult, R2: PartResult>(
+    input: &str,
+    func: impl Fn(&str) -> R1,
+    reference: impl Fn(&str) -> R2,
+) -> Option<(Outcome, Outcome)> {
+    let expected = run(reference, input);
+    if !expected.is_solved() {
+        return None;
+    }
+
+    let actual = run(func, input);
+    (actual != expected).then_some((actual, expected))
+}
+
+// Not part of the public API, used by the `differential!` macro.
+#[doc(hidden)]
+pub fn __check_examples<R1: PartResult, R2: PartResult>(
+    puzzle: PuzzleId,
+    part: u8,
+    func: impl Fn(&str) -> R1,
+    reference: impl Fn(&str) -> R2,
+) {
+    let files = example_files(puzzle);
+    assert!(!files.is_empty(), "day {puzzle} has no example files.");
+
+    for path in files {
+        if let Some((actual, expected)) = differs(&read_path(&path), &func, &reference) {
+            panic!(
+                "part {part} differs from the reference on \"{}\": expected {expected:?}, got {actual:?}",
+                path.display()
+            );
+        }
+    }
+}
+
+// Not part of the public API, used by the `differential!` macro.
+#[doc(hidden)]
+pub fn __check_generated<R1: PartResult, R2: PartResult>(
+    part: u8,
+    generator: Generator,
+    func: impl Fn(&str) -> R1,
+    reference: impl Fn(&str) -> R2,
+) {
+    let check = |case: Case| match differs(&case.generate(generator), &func, &reference) {
+        Some(difference) => Err(difference),
+        None => Ok(()),
+    };
+
+    for i in 0..CASES {
+        let case = Case {
+            seed: i as u64,
+            size: case_size(i, CASES, MAX_SIZE),
+        };
+
+        if let Err(difference) = check(case) {
+            let (case, (actual, expected)) = shrink(case, difference, check);
+            panic!(
+                "part {part} differs from the reference on a generated input (seed {}, size {}): expected {expected:?}, got {actual:?}\n\n{}",
+                case.seed,
+                case.size,
+                case.generate(generator)
+            );
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{differs, example_files, Outcome};
+    use crate::template::PuzzleId;
+    use crate::{day, year};
+
+    #[test]
+    fn finds_example_files() {
+        let files = example_files(PuzzleId::new(year!(2024), day!(9)));
+        let names: Vec<_> = files
+            .iter()
+            .filter_map(|path| path.file_name()?.to_str())
+            .collect();
+        assert_eq!(names, vec!["09-1.txt", "09-2.txt"]);
+    }
+
+    #[test]
+    fn compares_outcomes() {
+        let count = |input: &str| Some(input.len());
+        let wrong = |input: &str| Some(input.len() + 1);
+        let unsolved = |_: &str| None::<usize>;
+        let panics = |_: &str| -> Option<usize> { panic!("index out of bounds") };
+
+        assert_eq!(differs("abc", count, count), None);
+        assert_eq!(differs("abc", wrong, unsolved), None);
+        assert_eq!(
+            differs("abc", wrong, count),
+            Some((Outcome::Answer("4".into()), Outcome::Answer("3".into())))
+        );
+        assert_eq!(
+            differs("abc", panics, count),
+            Some((
+                Outcome::Panicked("index out of bounds".into()),
+                Outcome::Answer("3".into())
+            ))
+        );
+    }
+}
@@ -0,0 +1,354 @@
+/// Runs a solution on generated inputs, used by `cargo fuzz-day`.
+/// Every case is generated by the binary itself (see [`generate`](crate::template::generate)) and solved in a separate process with a timeout.
+/// A case fails if a part panics, returns an error, times out or disagrees with the reference implementation.
+/// Failing cases are shrunk to the smallest size that still fails and written to `target/fuzz/`.
+use std::fmt::Display;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::time::{Duration, Instant};
+use std::{fs, thread};
+
+use crate::template::generate::{case_size, shrink, Case, GENERATE_ARG, HOOKS_ARG, REFERENCE_ARG};
+use crate::template::run_inputs::{parse_output, ProfileOutcome};
+use crate::template::run_multi::child_commands::bin_path;
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Generated inputs with at most this many lines are printed next to the failure.
+const MAX_PRINTED_LINES: usize = 20;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    pub runs: usize,
+    pub max_size: usize,
+    pub seed: u64,
+    pub timeout: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The generator itself failed.
+    Generator(String),
+    Timeout(Duration),
+    /// A part panicked or returned an error.
+    Crash(String),
+    /// A part disagrees with the reference implementation.
+    Mismatch {
+        part: u8,
+        answer: Option<String>,
+        expected: Option<String>,
+    },
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let answer = |a: &Option<String>| a.clone().unwrap_or_else(|| "✖".into());
+        match self {
+            Failure::Generator(message) => write!(f, "generator failed: {message}"),
+            Failure::Timeout(timeout) => write!(f, "timed out after {timeout:.1?}"),
+            Failure::Crash(message) => f.write_str(message),
+            Failure::Mismatch {
+                part,
+                answer: actual,
+                expected,
+            } => write!(
+                f,
+                "part {part} answered {}, the reference answered {}",
+                answer(actual),
+                answer(expected)
+            ),
+        }
+    }
+}
+
+/// Reads the failures of a run from its outcome. Parts without an answer are only failures if they crashed.
+pub(crate) fn crashes(outcome: &ProfileOutcome) -> Result<(), Failure> {
+    if let Some(panic) = &outcome.panic {
+        return Err(Failure::Crash(format!("panicked: {panic}")));
+    }
+
+    for (i, part) in outcome.parts.iter().enumerate() {
+        if let Some(problem) = part
+            .problem
+            .as_ref()
+            .filter(|p| p.starts_with("panicked") || p.starts_with("failed"))
+        {
+            return Err(Failure::Crash(format!("part {} {problem}", i + 1)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Compares the answers of a run with the answers of the reference. Parts the reference does not answer are skipped.
+fn compare(outcome: &ProfileOutcome, reference: &ProfileOutcome) -> Result<(), Failure> {
+    for (part, (actual, expected)) in (1..).zip(outcome.parts.iter().zip(&reference.parts)) {
+        if expected.answer.is_some() && actual.answer != expected.answer {
+            return Err(Failure::Mismatch {
+                part,
+                answer: actual.answer.clone(),
+                expected: expected.answer.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A built solution binary.
+pub(crate) struct Solution {
+    bin: PathBuf,
+    pub(crate) has_reference: bool,
+    timeout: Duration,
+}
+
+impl Solution {
+    /// Builds the binary in release mode and checks which hooks it provides. Exits if it has no generator.
+    pub(crate) fn build(puzzle: PuzzleId, timeout: Duration) -> Self {
+        let bin_name = puzzle.bin_name();
+        let status = Command::new("cargo")
+            .args(["build", "--quiet", "--release", "--bin", &bin_name])
+            .status();
+
+        if !status.is_ok_and(|s| s.success()) {
+            eprintln!("Error: could not build {bin_name}.");
+            process::exit(1);
+        }
+
+        let bin = bin_path(puzzle, true);
+        let hooks = Command::new(&bin)
+            .arg(HOOKS_ARG)
+            .output()
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+            .unwrap_or_default();
+
+        if !hooks.lines().any(|l| l == "generate") {
+            eprintln!("Error: day {puzzle} has no input generator. Add one with `solution!({}, {}, generate = generate)`, see the readme.", puzzle.year, puzzle.day);
+            process::exit(1);
+        }
+
+        Solution {
+            bin,
+            has_reference: hooks.lines().any(|l| l == "reference"),
+            timeout,
+        }
+    }
+
+    pub(crate) fn generate(&self, case: Case) -> Result<String, Failure> {
+        let output = Command::new(&self.bin)
+            .args([GENERATE_ARG, &case.seed.to_string(), &case.size.to_string()])
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| Failure::Generator(e.to_string()))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = stderr
+                .lines()
+                .skip_while(|l| !l.contains("panicked at"))
+                .nth(1)
+                .unwrap_or("non-zero exit status");
+            return Err(Failure::Generator(message.trim().to_string()));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Solves `input`, killing the process if it takes longer than the timeout.
+    pub(crate) fn run(&self, input: &str, args: &[&str]) -> Result<ProfileOutcome, Failure> {
+        let mut child = Command::new(&self.bin)
+            .args(["--input", "-"])
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| Failure::Crash(format!("could not run {}: {e}", self.bin.display())))?;
+
+        // write from a thread, a solution that does not read its input would block a large write.
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let input = input.to_string();
+        thread::spawn(move || {
+            let _ = stdin.write_all(input.as_bytes());
+        });
+
+        let stdout = read_pipe(child.stdout.take());
+        let stderr = read_pipe(child.stderr.take());
+
+        let timer = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait().ok().flatten() {
+                break status;
+            }
+            if timer.elapsed() > self.timeout {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(Failure::Timeout(self.timeout));
+            }
+            thread::sleep(Duration::from_millis(1));
+        };
+
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+        Ok(parse_output(
+            "generated".into(),
+            &stdout,
+            &stderr,
+            status.success(),
+        ))
+    }
+
+    fn check(&self, case: Case) -> Result<(), Failure> {
+        let input = self.generate(case)?;
+        let outcome = self.run(&input, &[])?;
+        crashes(&outcome)?;
+
+        if self.has_reference {
+            let reference = self.run(&input, &[REFERENCE_ARG]).map_err(|e| match e {
+                Failure::Timeout(_) => e,
+                e => Failure::Crash(format!("reference {e}")),
+            })?;
+            crashes(&reference).map_err(|e| Failure::Crash(format!("reference {e}")))?;
+            compare(&outcome, &reference)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn read_pipe(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut s = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut s);
+        }
+        s
+    })
+}
+
+fn failure_path(puzzle: PuzzleId, case: Case) -> PathBuf {
+    PathBuf::from("target").join("fuzz").join(format!(
+        "{}-seed{}-size{}.txt",
+        puzzle.bin_name(),
+        case.seed,
+        case.size
+    ))
+}
+
+fn write_failure(path: &Path, input: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, input)
+}
+
+/// Fuzzes a solution. Returns `false` if a failing case was found.
+pub fn fuzz(puzzle: PuzzleId, options: Options) -> bool {
+    let solution = Solution::build(puzzle, options.timeout);
+
+    let compared = if solution.has_reference {
+        ", comparing against the reference"
+    } else {
+        ""
+    };
+    println!(
+        "Fuzzing day {puzzle} with {} cases of size 1 to {} (seed {}){compared}.",
+        options.runs, options.max_size, options.seed
+    );
+
+    for i in 0..options.runs {
+        let case = Case {
+            seed: options.seed.wrapping_add(i as u64),
+            size: case_size(i, options.runs, options.max_size),
+        };
+
+        print!(
+            "\r{ANSI_ITALIC}case {}/{} (size {}){ANSI_RESET}",
+            i + 1,
+            options.runs,
+            case.size
+        );
+        let _ = std::io::stdout().flush();
+
+        let Err(failure) = solution.check(case) else {
+            continue;
+        };
+
+        println!(
+            "\r{ANSI_BOLD}Failure:{ANSI_RESET} {failure} (seed {}, size {}).",
+            case.seed, case.size
+        );
+        print!("{ANSI_ITALIC}shrinking{ANSI_RESET}");
+        let _ = std::io::stdout().flush();
+
+        let (case, failure) = shrink(case, failure, |case| solution.check(case));
+        report(&solution, puzzle, case, &failure);
+        return false;
+    }
+
+    println!("\rNo failures in {} cases.          ", options.runs);
+    true
+}
+
+fn report(solution: &Solution, puzzle: PuzzleId, case: Case, failure: &Failure) {
+    println!(
+        "\r{ANSI_BOLD}Smallest failure:{ANSI_RESET} {failure} (seed {}, size {}).",
+        case.seed, case.size
+    );
+
+    let Ok(input) = solution.generate(case) else {
+        return;
+    };
+
+    if input.lines().count() <= MAX_PRINTED_LINES {
+        println!();
+        for line in input.lines() {
+            println!("    {line}");
+        }
+        println!();
+    }
+
+    let path = failure_path(puzzle, case);
+    match write_failure(&path, &input) {
+        Ok(()) => println!(
+            "Wrote the input to \"{}\", reproduce with `cargo solve {puzzle} --input {}`.",
+            path.display(),
+            path.display()
+        ),
+        Err(e) => eprintln!("Failed to write the input: {e}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, crashes, Failure};
+    use crate::template::run_inputs::parse_output;
+
+    #[test]
+    fn detects_crashes_and_mismatches() {
+        let outcome = |stdout: &str| parse_output("generated".into(), stdout, "", true);
+
+        let solved = outcome("Part 1: 11 (7.3µs)\nPart 2: 42 (1.0µs)\n");
+        let reference = outcome("Part 1: 11 (1.3ms)\nPart 2: ✖ not implemented\n");
+        assert_eq!(crashes(&solved), Ok(()));
+        assert_eq!(crashes(&reference), Ok(()));
+        assert_eq!(compare(&solved, &reference), Ok(()));
+
+        let wrong = outcome("Part 1: 12 (7.3µs)\nPart 2: 42 (1.0µs)\n");
+        assert_eq!(
+            compare(&wrong, &reference).unwrap_err().to_string(),
+            "part 1 answered 12, the reference answered 11"
+        );
+
+        let crashed = outcome("Part 1: ✖ panicked: attempt to subtract with overflow\n");
+        assert_eq!(
+            crashes(&crashed),
+            Err(Failure::Crash(
+                "part 1 panicked: attempt to subtract with overflow".into()
+            ))
+        );
+    }
+}
@@ -0,0 +1,217 @@
+/// Hooks of a solution binary that are used by `cargo fuzz-day`.
+/// A day opts in with `solution!(2024, 13, generate = generate)`, where `generate(rng, size)` returns a random, valid puzzle input.
+/// `reference = <module>` names a module with naive `part_one` and `part_two` functions, whose answers the solution is compared against.
+use std::{env, process};
+
+/// Passed to a solution binary to print a generated input, followed by the seed and the size.
+pub const GENERATE_ARG: &str = "--generate";
+
+/// Passed to a solution binary to run the parts of its reference module instead.
+pub const REFERENCE_ARG: &str = "--reference";
+
+/// Passed to a solution binary to list the hooks it provides, one per line.
+pub const HOOKS_ARG: &str = "--hooks";
+
+/// Number of seeds tried for every size while shrinking.
+const SHRINK_SEEDS: u64 = 8;
+
+/// Generates a puzzle input of roughly `size` items (lines, machines, ...) from a random number generator.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// A generated input, identified by the seed and size it was generated from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Case {
+    pub seed: u64,
+    pub size: usize,
+}
+
+impl Case {
+    pub fn generate(self, generator: Generator) -> String {
+        generator(&mut Rng::new(self.seed), self.size)
+    }
+}
+
+/// Size of the `i`-th of `runs` cases. Sizes grow from 1 to `max_size`, so simple inputs are tried first.
+pub fn case_size(i: usize, runs: usize, max_size: usize) -> usize {
+    1 + i * max_size.saturating_sub(1) / runs.saturating_sub(1).max(1)
+}
+
+/// Looks for the smallest size that still fails, trying a few seeds per size, starting with the seed of `case`.
+pub fn shrink<F>(
+    case: Case,
+    failure: F,
+    mut check: impl FnMut(Case) -> Result<(), F>,
+) -> (Case, F) {
+    for size in 1..case.size {
+        for seed in case.seed..case.seed.saturating_add(SHRINK_SEEDS) {
+            let smaller = Case { seed, size };
+            if let Err(failure) = check(smaller) {
+                return (smaller, failure);
+            }
+        }
+    }
+    (case, failure)
+}
+
+/// A small, seedable random number generator (xorshift64*), so generated inputs can be reproduced from their seed.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // splitmix64 spreads similar seeds apart and avoids the all-zero state.
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        Rng {
+            state: (z ^ (z >> 31)).max(1),
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Returns a number in `low..high`. Panics if the range is empty.
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low < high, "empty range {low}..{high}");
+        low + self.next_u64() % (high - low)
+    }
+
+    /// Returns an index into a collection of `len` items.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0, len as u64) as usize
+    }
+
+    /// Returns `true` with a probability of `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.range(0, denominator) < numerator
+    }
+
+    /// Picks a random item. Panics if `items` is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Shuffles `items` in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.index(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Reads the seed and size from the `--generate` argument of a solution binary.
+pub fn generate_args() -> Option<(u64, usize)> {
+    let args: Vec<String> = env::args().collect();
+    args.windows(3)
+        .find(|w| w[0] == GENERATE_ARG)
+        .and_then(|w| Some((w[1].parse().ok()?, w[2].parse().ok()?)))
+}
+
+/// Returns `true` if a solution binary is asked to run its reference implementation.
+pub fn is_reference() -> bool {
+    env::args().any(|x| x == REFERENCE_ARG)
+}
+
+// Not part of the public API, used by the `solution!` macro.
+// Handles the hook arguments before the input is read, returns `true` if the reference implementation should run.
+#[doc(hidden)]
+pub fn __handle_hook_args(generator: Option<Generator>, has_reference: bool) -> bool {
+    if env::args().any(|x| x == HOOKS_ARG) {
+        if generator.is_some() {
+            println!("generate");
+        }
+        if has_reference {
+            println!("reference");
+        }
+        process::exit(0);
+    }
+
+    if let Some((seed, size)) = generate_args() {
+        let Some(generator) = generator else {
+            eprintln!("Error: this day has no input generator, add one with `solution!(<year>, <day>, generate = generate)`.");
+            process::exit(1);
+        };
+        print!("{}", Case { seed, size }.generate(generator));
+        process::exit(0);
+    }
+
+    let is_reference = is_reference();
+    if is_reference && !has_reference {
+        eprintln!("Error: this day has no reference implementation, add one with `solution!(<year>, <day>, reference = reference)`.");
+        process::exit(1);
+    }
+
+    is_reference
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{case_size, shrink, Case, Rng};
+
+    #[test]
+    fn is_reproducible() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..8).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+        assert_ne!(numbers(0), vec![0; 8]);
+    }
+
+    #[test]
+    fn stays_in_range() {
+        let mut rng = Rng::new(42);
+        for _ in 0..1000 {
+            assert!((10..20).contains(&rng.range(10, 20)));
+            assert!(rng.index(3) < 3);
+        }
+        assert!(!rng.chance(0, 5));
+        assert!(rng.chance(5, 5));
+    }
+
+    #[test]
+    fn grows_case_sizes() {
+        let sizes: Vec<usize> = (0..5).map(|i| case_size(i, 5, 100)).collect();
+        assert_eq!(sizes, vec![1, 25, 50, 75, 100]);
+        assert_eq!(case_size(0, 1, 100), 1);
+        assert_eq!(case_size(3, 10, 0), 1);
+    }
+
+    #[test]
+    fn shrinks_to_smallest_failing_size() {
+        let case = Case { seed: 40, size: 90 };
+
+        // fails from size 12 on, but only for odd seeds.
+        let (smallest, failure) = shrink(case, 90, |c| {
+            if c.size >= 12 && c.seed % 2 == 1 {
+                Err(c.size)
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!((smallest, failure), (Case { seed: 41, size: 12 }, 12));
+        assert_eq!(shrink(case, 90, |_| Ok(())), (case, 90));
+    }
+
+    #[test]
+    fn shuffles_all_items() {
+        let mut rng = Rng::new(1);
+        let mut items: Vec<u32> = (0..50).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+    }
+}
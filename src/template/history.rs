@@ -0,0 +1,366 @@
+/// Module that keeps an append-only history of benchmark runs.
+/// Every `cargo time --store` appends a [`Record`] so runtime changes can be traced back to commits.
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::timings::{format_nanos, Timing, Timings};
+use crate::template::{year_dir, Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const HISTORY_FILE_NAME: &str = "timings_history.json";
+
+fn file_path(year: Year) -> PathBuf {
+    year_dir(year).join(HISTORY_FILE_NAME)
+}
+
+const CHART_WIDTH: f64 = 40.0;
+
+/// A single benchmark run, along with the environment it was recorded in.
+#[derive(Clone, Debug)]
+pub struct Record {
+    pub commit: Option<String>,
+    pub timestamp: u64,
+    pub machine: String,
+    pub profile: String,
+    pub timings: Timings,
+}
+
+/// Represents every stored benchmark run, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub records: Vec<Record>,
+}
+
+impl Record {
+    /// Create a record for `timings` from the current environment.
+    pub fn new(timings: Timings, is_release: bool) -> Self {
+        Record {
+            commit: get_commit(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            machine: get_machine_id(),
+            profile: if is_release { "release" } else { "debug" }.into(),
+            timings,
+        }
+    }
+}
+
+impl History {
+    /// Dehydrate history to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let path = file_path(year);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate history from a JSON file. If not present, returns an empty history.
+    /// Fails if the file can not be read or parsed, so a damaged history is never overwritten.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = file_path(year);
+        match fs::read_to_string(&path) {
+            Ok(json) => History::try_from(json).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    pub fn push(&mut self, record: Record) {
+        self.records.push(record);
+    }
+
+    /// Returns every recorded timing of `day` along with the record it belongs to.
+    pub fn for_day(&self, day: Day) -> Vec<(&Record, &Timing)> {
+        self.records
+            .iter()
+            .filter_map(|r| Some((r, r.timings.data.iter().find(|t| t.day == day)?)))
+            .collect()
+    }
+}
+
+/// Print a bar chart of how the runtime of each part of `puzzle` evolved.
+pub fn print_chart(history: &History, puzzle: PuzzleId) {
+    let entries = history.for_day(puzzle.day);
+
+    println!(
+        "{ANSI_BOLD}{} Day {} history{ANSI_RESET}",
+        puzzle.year, puzzle.day
+    );
+    println!("------");
+
+    if entries.is_empty() {
+        println!("No stored timings. Run `cargo time {puzzle} --store` to record some.");
+        return;
+    }
+
+    for part in [1, 2] {
+        let values: Vec<_> = entries
+            .iter()
+            .map(|(record, timing)| {
+                let nanos = if part == 1 {
+                    timing.part_1
+                } else {
+                    timing.part_2
+                };
+                (record, nanos)
+            })
+            .collect();
+
+        let max = values
+            .iter()
+            .filter_map(|(_, nanos)| *nanos)
+            .fold(0_f64, f64::max);
+
+        println!("\n{ANSI_BOLD}Part {part}{ANSI_RESET}");
+
+        for (record, nanos) in values {
+            let label = format!(
+                "{} {:>8} {}",
+                format_date(record.timestamp),
+                record.commit.as_deref().unwrap_or("-"),
+                record.profile
+            );
+
+            match nanos {
+                Some(nanos) => {
+                    let bar = "█".repeat(bar_length(nanos, max));
+                    println!(
+                        "{label} {bar} {ANSI_ITALIC}{}{ANSI_RESET}",
+                        format_nanos(nanos)
+                    );
+                }
+                None => println!("{label} ✖"),
+            }
+        }
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn bar_length(value: f64, max: f64) -> usize {
+    if max <= 0_f64 {
+        return 0;
+    }
+    ((value / max * CHART_WIDTH).round() as usize).max(1)
+}
+
+fn get_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!commit.is_empty()).then_some(commit)
+}
+
+/// Identifies the machine a benchmark ran on.
+/// Can be set explicitly with `AOC_MACHINE_ID`, falls back to the hostname.
+fn get_machine_id() -> String {
+    ["AOC_MACHINE_ID", "HOSTNAME", "COMPUTERNAME"]
+        .iter()
+        .find_map(|key| env::var(key).ok().filter(|v| !v.is_empty()))
+        .or_else(|| {
+            fs::read_to_string("/etc/hostname")
+                .ok()
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        })
+        .unwrap_or_else(|| "unknown".into())
+}
+
+/// Formats a unix timestamp as `YYYY-MM-DD` (UTC).
+fn format_date(timestamp: u64) -> String {
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    #[allow(clippy::cast_possible_wrap)]
+    let z = (timestamp / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<History> for JsonValue {
+    fn from(value: History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "records".into(),
+            JsonValue::Array(value.records.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_records = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("records")
+            .ok_or("expected JSON document to have key `records`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.records` to be an array.")?;
+
+        Ok(History {
+            records: json_records
+                .iter()
+                .map(Record::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Record> for JsonValue {
+    fn from(value: &Record) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("machine".into(), JsonValue::String(value.machine.clone()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "commit".into(),
+            match &value.commit {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Record {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected record.timestamp to be a number.")? as u64;
+
+        let machine = json
+            .get("machine")
+            .and_then(|v| v.get::<String>().cloned())
+            .ok_or("Expected record.machine to be a string.")?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>().cloned())
+            .ok_or("Expected record.profile to be a string.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| v.get::<String>().cloned())
+            .ok_or("Expected record.commit to be null or string.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected record.data to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Record {
+            commit,
+            timestamp,
+            machine,
+            profile,
+            timings: Timings { data },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bar_length, format_date, History};
+    use crate::day;
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(1_733_011_200), "2024-12-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+    }
+
+    #[test]
+    fn scales_bars() {
+        assert_eq!(bar_length(10.0, 10.0), 40);
+        assert_eq!(bar_length(5.0, 10.0), 20);
+        assert_eq!(bar_length(0.001, 10.0), 1);
+    }
+
+    #[test]
+    fn round_trips_records() {
+        let json = r#"{ "records": [
+            { "timestamp": 1733011200, "machine": "box", "profile": "release", "commit": "abc1234",
+              "data": [{ "day": "01", "part_1": 1000, "part_2": null, "total_nanos": 1000 }] },
+            { "timestamp": 1733097600, "machine": "box", "profile": "release", "commit": null,
+              "data": [{ "day": "02", "part_1": 2000, "part_2": 3000, "total_nanos": 5000 }] }
+        ] }"#
+            .to_string();
+
+        let history = History::try_from(json).unwrap();
+        assert_eq!(history.records.len(), 2);
+        assert_eq!(history.records[0].commit.as_deref(), Some("abc1234"));
+        assert_eq!(history.records[1].commit, None);
+        assert_eq!(history.for_day(day!(1)).len(), 1);
+        assert_eq!(history.for_day(day!(3)).len(), 0);
+
+        let serialized = tinyjson::JsonValue::from(history).stringify().unwrap();
+        let history = History::try_from(serialized).unwrap();
+        assert_eq!(history.records.len(), 2);
+        assert_eq!(history.records[1].timings.data[0].part_2, Some(3000_f64));
+    }
+
+    #[test]
+    fn rejects_truncated_history() {
+        let json = r#"{ "records": [{ "timestamp": 1733011200, "machine": "box""#;
+        assert!(History::try_from(json.to_string()).is_err());
+    }
+}
@@ -0,0 +1,544 @@
+/// Reads the JSON of a private leaderboard and analyzes it offline.
+/// The JSON is served at `https://adventofcode.com/<year>/leaderboard/private/view/<id>.json`.
+/// Local scores are recomputed from the star timestamps under the official rules.
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{all_days, Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Puzzles unlock at midnight EST, i.e. 05:00 UTC.
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+const CHART_HEIGHT: usize = 10;
+
+/// Only the top of the standings is drawn, so the markers of the chart stay distinguishable.
+const CHART_MEMBERS: usize = 8;
+
+/// A star of a member. Stars with the same timestamp are ordered by their index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Star {
+    pub timestamp: u64,
+    pub index: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Member {
+    pub id: String,
+    pub name: Option<String>,
+    /// The local score reported by the API.
+    pub local_score: Option<u64>,
+    pub stars: BTreeMap<(Day, u8), Star>,
+}
+
+/// A private leaderboard of one year.
+#[derive(Clone, Debug)]
+pub struct Leaderboard {
+    pub year: Year,
+    pub members: Vec<Member>,
+}
+
+impl Member {
+    /// The name of the member, as shown on the website.
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+impl Leaderboard {
+    /// Selects a member by id. Without an `id`, the leaderboard needs to have exactly one member.
+    pub fn member(&self, id: Option<&str>) -> Result<&Member, String> {
+        match id {
+            Some(id) => self
+                .members
+                .iter()
+                .find(|m| m.id == id.trim())
+                .ok_or_else(|| format!("leaderboard has no member with id `{id}`.")),
+            None if self.members.len() == 1 => Ok(&self.members[0]),
+            None => Err(format!(
+                "leaderboard has {} members, pass the id of yours.",
+                self.members.len()
+            )),
+        }
+    }
+
+    /// Points of every star of every member, in the order of `members`.
+    /// With `n` members, the first to get a star receives `n` points, the second `n - 1`, and so on.
+    pub fn points(&self) -> Vec<HashMap<(Day, u8), u64>> {
+        let mut points = vec![HashMap::new(); self.members.len()];
+
+        let mut finishers: BTreeMap<(Day, u8), Vec<(Star, usize)>> = BTreeMap::new();
+        for (i, member) in self.members.iter().enumerate() {
+            for (key, star) in &member.stars {
+                finishers.entry(*key).or_default().push((*star, i));
+            }
+        }
+
+        let n = self.members.len() as u64;
+        for (key, mut stars) in finishers {
+            stars.sort_unstable_by_key(|(star, i)| (star.timestamp, star.index, *i));
+            for (rank, (_, i)) in stars.into_iter().enumerate() {
+                points[i].insert(key, n - rank as u64);
+            }
+        }
+
+        points
+    }
+
+    /// Local score of every member, counting the stars obtained before `until` (a unix timestamp).
+    fn scores_until(&self, points: &[HashMap<(Day, u8), u64>], until: u64) -> Vec<u64> {
+        self.members
+            .iter()
+            .zip(points)
+            .map(|(member, points)| {
+                member
+                    .stars
+                    .iter()
+                    .filter(|(_, star)| star.timestamp < until)
+                    .map(|(key, _)| points[key])
+                    .sum()
+            })
+            .collect()
+    }
+
+    /// Indices of the members, ordered by local score, then by stars.
+    fn standings(&self, scores: &[u64]) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.members.len()).collect();
+        order.sort_by_key(|&i| {
+            (
+                std::cmp::Reverse(scores[i]),
+                std::cmp::Reverse(self.members[i].stars.len()),
+                self.members[i].display_name(),
+            )
+        });
+        order
+    }
+
+    /// The last day of the event any member got a star on.
+    fn last_day(&self) -> Option<Day> {
+        self.members
+            .iter()
+            .flat_map(|m| m.stars.keys().map(|(day, _)| *day))
+            .filter(|day| self.year.contains(*day))
+            .max()
+    }
+
+    /// Renders the standings with the recomputed local scores.
+    pub fn standings_lines(&self) -> Vec<String> {
+        let points = self.points();
+        let scores = self.scores_until(&points, u64::MAX);
+
+        let mut lines = vec!["  # Score Stars  Name".to_string()];
+        for (rank, i) in self.standings(&scores).into_iter().enumerate() {
+            let member = &self.members[i];
+            let mut line = format!(
+                "{:>3} {:>5} {:>5}  {}",
+                rank + 1,
+                scores[i],
+                member.stars.len(),
+                member.display_name()
+            );
+            if let Some(reported) = member.local_score.filter(|s| *s != scores[i]) {
+                line.push_str(&format!(" (API: {reported})"));
+            }
+            lines.push(line);
+        }
+        lines
+    }
+
+    /// Renders the time every star of `member` took after the puzzle unlocked, and the time between both parts.
+    pub fn member_lines(&self, member: &Member) -> Vec<String> {
+        let mut lines = vec![format!(
+            "{:>3}  {:>10}  {:>10}  {:>10}",
+            "Day", "Part 1", "Part 2", "Delta"
+        )];
+
+        let days: Vec<Day> = all_days(self.year)
+            .filter(|day| member.stars.contains_key(&(*day, 1)))
+            .collect();
+
+        for day in days {
+            let unlock = unlock_time(self.year, day);
+            let [part_1, part_2] = [1, 2].map(|part| member.stars.get(&(day, part)));
+            let since_unlock =
+                |star: Option<&Star>| star.map(|s| s.timestamp.saturating_sub(unlock));
+
+            let delta = part_1
+                .zip(part_2)
+                .map(|(p1, p2)| p2.timestamp.saturating_sub(p1.timestamp));
+
+            lines.push(format!(
+                "{:>3}  {:>10}  {:>10}  {:>10}",
+                day.into_inner(),
+                format_duration(since_unlock(part_1)),
+                format_duration(since_unlock(part_2)),
+                format_duration(delta)
+            ));
+        }
+
+        lines
+    }
+
+    /// Renders a chart of the local score of the top members at the end of every day of the event.
+    /// The last day includes every later star, so it matches the standings.
+    pub fn chart_lines(&self) -> Vec<String> {
+        let Some(last_day) = self.last_day() else {
+            return vec!["No stars yet.".into()];
+        };
+
+        let days: Vec<Day> = all_days(self.year).take_while(|d| *d <= last_day).collect();
+        let points = self.points();
+
+        let columns: Vec<Vec<u64>> = days
+            .iter()
+            .map(|day| {
+                let until = if *day == last_day {
+                    u64::MAX
+                } else {
+                    unlock_time(self.year, *day) + 86_400
+                };
+                self.scores_until(&points, until)
+            })
+            .collect();
+
+        let final_scores = columns.last().cloned().unwrap_or_default();
+        let members: Vec<usize> = self
+            .standings(&final_scores)
+            .into_iter()
+            .take(CHART_MEMBERS)
+            .collect();
+
+        let max = final_scores.iter().copied().max().unwrap_or(0).max(1);
+        let marker = |position: usize| char::from(b'A' + position as u8);
+
+        let mut lines = vec![];
+        for row in (1..=CHART_HEIGHT).rev() {
+            let label = match row {
+                CHART_HEIGHT => max.to_string(),
+                _ => String::new(),
+            };
+            let mut line = format!("{label:>5} |");
+            for column in &columns {
+                let cell: Vec<char> = members
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &i)| chart_row(column[i], max) == row)
+                    .map(|(position, _)| marker(position))
+                    .collect();
+                line.push_str(
+                    match cell.len() {
+                        0 => "   ".into(),
+                        1 => format!("  {}", cell[0]),
+                        _ => "  *".into(),
+                    }
+                    .as_str(),
+                );
+            }
+            lines.push(line.trim_end().to_string());
+        }
+
+        lines.push(format!("{:>5} +{}", 0, "---".repeat(columns.len())));
+        lines.push(format!(
+            "{:>5}  {}",
+            "Day",
+            days.iter()
+                .map(|d| format!("{:>3}", d.into_inner()))
+                .collect::<String>()
+        ));
+        lines.push(String::new());
+        lines.push(
+            members
+                .iter()
+                .enumerate()
+                .map(|(position, &i)| {
+                    format!("{} {}", marker(position), self.members[i].display_name())
+                })
+                .collect::<Vec<_>>()
+                .join("  "),
+        );
+        lines.push("Members on the same spot are drawn as `*`.".into());
+
+        lines
+    }
+}
+
+/// Print the standings, the stars of every member and a chart of the scores over time.
+pub fn print(leaderboard: &Leaderboard) {
+    println!(
+        "{ANSI_BOLD}{} private leaderboard{ANSI_RESET}",
+        leaderboard.year
+    );
+    println!("------");
+    for line in leaderboard.standings_lines() {
+        println!("{line}");
+    }
+
+    for member in &leaderboard.members {
+        if member.stars.is_empty() {
+            continue;
+        }
+        println!();
+        println!(
+            "{ANSI_BOLD}{}{ANSI_RESET} {ANSI_ITALIC}(time after unlock){ANSI_RESET}",
+            member.display_name()
+        );
+        for line in leaderboard.member_lines(member) {
+            println!("{line}");
+        }
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Score over time{ANSI_RESET}");
+    for line in leaderboard.chart_lines() {
+        println!("{line}");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Row of a score in the chart, from `1` (lowest) to [`CHART_HEIGHT`].
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn chart_row(score: u64, max: u64) -> usize {
+    ((score as f64 / max as f64 * (CHART_HEIGHT - 1) as f64).round() as usize) + 1
+}
+
+/// Unix timestamp of the unlock of a puzzle.
+pub fn unlock_time(year: Year, day: Day) -> u64 {
+    days_from_civil(
+        i64::from(year.into_inner()),
+        12,
+        i64::from(day.into_inner()),
+    ) * 86_400
+        + UNLOCK_HOUR_UTC * 3600
+}
+
+/// Days since the unix epoch of a date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> u64 {
+    // see: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    u64::try_from(era * 146_097 + doe - 719_468).unwrap_or_default()
+}
+
+/// Formats a duration in seconds as `hh:mm:ss`, or `-` if there is none.
+fn format_duration(secs: Option<u64>) -> String {
+    secs.map_or_else(
+        || "-".into(),
+        |secs| {
+            format!(
+                "{:02}:{:02}:{:02}",
+                secs / 3600,
+                secs % 3600 / 60,
+                secs % 60
+            )
+        },
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let year = json
+            .get("event")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| v.parse().ok())
+            .ok_or("expected `json.event` to be a year.")?;
+
+        let mut members = json
+            .get("members")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected `json.members` to be an object.")?
+            .iter()
+            .map(|(id, member)| Member::try_from((id.as_str(), member)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        members.sort_by(|a, b| a.id.cmp(&b.id));
+
+        Ok(Leaderboard { year, members })
+    }
+}
+
+impl TryFrom<(&str, &JsonValue)> for Member {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from((id, value): (&str, &JsonValue)) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected member to be a JSON object.")?;
+
+        let name = json.get("name").and_then(|v| v.get::<String>()).cloned();
+
+        let local_score = json
+            .get("local_score")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64);
+
+        let completed = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected `member.completion_day_level` to be an object.")?;
+
+        let mut stars = BTreeMap::new();
+        for (day, parts) in completed {
+            let day = Day::from_str(day).map_err(|_| format!("invalid day `{day}`."))?;
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected the parts of a day to be an object.")?;
+
+            for (part, star) in parts {
+                let part = part
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|part| (1..=2).contains(part))
+                    .ok_or_else(|| format!("invalid part `{part}`."))?;
+
+                let star = star
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or("expected a star to be an object.")?;
+                let number = |key: &str| star.get(key).and_then(|v| v.get::<f64>()).copied();
+
+                stars.insert(
+                    (day, part),
+                    Star {
+                        timestamp: number("get_star_ts")
+                            .ok_or("expected `star.get_star_ts` to be a number.")?
+                            as u64,
+                        index: number("star_index").unwrap_or_default() as u64,
+                    },
+                );
+            }
+        }
+
+        Ok(Member {
+            id: id.to_string(),
+            name,
+            local_score,
+            stars,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_duration, unlock_time, Leaderboard};
+    use crate::{day, year};
+
+    /// Three members: `1` and `2` solved day 1, `2` was faster. Only `1` solved day 2. `3` is anonymous.
+    const LEADERBOARD: &str = r#"{
+        "event": "2024",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "local_score": 8, "stars": 3,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1733029500, "star_index": 10 },
+                        "2": { "get_star_ts": 1733030100, "star_index": 30 }
+                    },
+                    "2": { "1": { "get_star_ts": 1733119200, "star_index": 50 } }
+                }
+            },
+            "2": {
+                "id": 2, "name": "bob", "local_score": 6, "stars": 2,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1733029500, "star_index": 5 },
+                        "2": { "get_star_ts": 1733029800, "star_index": 20 }
+                    }
+                }
+            },
+            "3": { "id": 3, "name": null, "local_score": 0, "stars": 0, "completion_day_level": {} }
+        }
+    }"#;
+
+    fn leaderboard() -> Leaderboard {
+        Leaderboard::try_from(LEADERBOARD.to_string()).unwrap()
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        // 2024-12-01T05:00:00Z
+        assert_eq!(unlock_time(year!(2024), day!(1)), 1_733_029_200);
+        assert_eq!(unlock_time(year!(2015), day!(25)), 1_451_019_600);
+    }
+
+    #[test]
+    fn recomputes_local_scores() {
+        let leaderboard = leaderboard();
+        assert_eq!(leaderboard.year, year!(2024));
+
+        let points = leaderboard.points();
+        // ties are broken by the star index.
+        assert_eq!(points[0][&(day!(1), 1)], 2);
+        assert_eq!(points[1][&(day!(1), 1)], 3);
+        assert_eq!(points[0][&(day!(2), 1)], 3);
+
+        assert_eq!(
+            leaderboard.standings_lines(),
+            vec![
+                "  # Score Stars  Name",
+                "  1     7     3  alice (API: 8)",
+                "  2     6     2  bob",
+                "  3     0     0  (anonymous user #3)",
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_member_times() {
+        let leaderboard = leaderboard();
+        assert_eq!(
+            leaderboard.member_lines(&leaderboard.members[0]),
+            vec![
+                "Day      Part 1      Part 2       Delta",
+                "  1    00:05:00    00:15:00    00:10:00",
+                "  2    01:00:00           -           -",
+            ]
+        );
+        assert_eq!(format_duration(Some(90_061)), "25:01:01");
+    }
+
+    #[test]
+    fn renders_chart() {
+        let lines = leaderboard().chart_lines();
+        assert_eq!(lines.len(), 10 + 5);
+        assert_eq!(lines[0], "    7 |     A");
+        assert_eq!(lines[1], "      |  B  B");
+        assert_eq!(lines[4], "      |  A");
+        assert_eq!(lines[9], "      |  C  C");
+        assert_eq!(lines[10], "    0 +------");
+        assert_eq!(lines[11], "  Day    1  2");
+        assert_eq!(lines[13], "A alice  B bob  C (anonymous user #3)");
+    }
+
+    #[test]
+    fn selects_members() {
+        let leaderboard = leaderboard();
+        assert_eq!(leaderboard.member(Some("2")).unwrap().display_name(), "bob");
+        assert!(leaderboard.member(None).is_err());
+        assert!(leaderboard.member(Some("4")).is_err());
+    }
+}
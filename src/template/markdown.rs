@@ -0,0 +1,166 @@
+/// Minimal HTML to markdown conversion for puzzle descriptions.
+/// Only handles the handful of tags used on adventofcode.com puzzle pages.
+use std::fmt::Write;
+
+/// Converts every `<article>` of a puzzle page to markdown.
+pub fn puzzle_to_markdown(html: &str) -> String {
+    articles(html)
+        .iter()
+        .map(|article| html_to_markdown(article))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Returns the inner HTML of every `<article>` element.
+pub fn articles(html: &str) -> Vec<&str> {
+    let mut res = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(len) = rest[content_start..].find("</article>") else {
+            break;
+        };
+        res.push(&rest[content_start..content_start + len]);
+        rest = &rest[content_start + len..];
+    }
+
+    res
+}
+
+/// Converts a fragment of HTML to markdown.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut links: Vec<String> = vec![];
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut out, &rest[..tag_start], in_pre);
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            push_text(&mut out, &rest[tag_start..], in_pre);
+            break;
+        };
+
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("")
+            .to_ascii_lowercase();
+
+        match (name.as_str(), is_closing) {
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) if !in_pre => out.push('*'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("a", false) => {
+                links.push(attribute(tag, "href").unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                let _ = write!(out, "]({href})");
+            }
+            _ => {}
+        }
+    }
+
+    out.trim().to_string()
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+    if in_pre {
+        out.push_str(&text);
+    } else {
+        // outside of code blocks, newlines in the source carry no meaning.
+        let text = text.replace('\n', " ");
+        if out.is_empty() || out.ends_with('\n') {
+            out.push_str(text.trim_start());
+        } else {
+            out.push_str(&text);
+        }
+    }
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = tag[start..].find('"')?;
+    Some(decode_entities(&tag[start..start + len]))
+}
+
+/// Decodes the HTML entities that appear in puzzle texts.
+pub fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{articles, html_to_markdown, puzzle_to_markdown};
+
+    #[test]
+    fn converts_puzzle_description() {
+        let html = r#"<h2>--- Day 1: Historian Hysteria ---</h2><p>The <em>Chief Historian</em> is
+always present. See <a href="/2024/about">about</a>.</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<ul><li>Pair <code>1</code> &amp; <code>3</code></li></ul>"#;
+
+        let expected = [
+            "## --- Day 1: Historian Hysteria ---",
+            "",
+            "The *Chief Historian* is always present. See [about](/2024/about).",
+            "",
+            "```",
+            "3   4",
+            "4   3",
+            "```",
+            "",
+            "- Pair `1` & `3`",
+        ]
+        .join("\n");
+
+        assert_eq!(html_to_markdown(html), expected);
+    }
+
+    #[test]
+    fn extracts_articles() {
+        let html = r#"<main><article class="day-desc"><p>one</p></article><p>skip</p><article class="day-desc"><p>two</p></article></main>"#;
+        assert_eq!(articles(html), vec!["<p>one</p>", "<p>two</p>"]);
+        assert_eq!(puzzle_to_markdown(html), "one\n\ntwo");
+    }
+}
@@ -1,68 +1,512 @@
-use std::{env, fs};
+use std::fmt::Debug;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::{env, fs, process};
 
-pub mod aoc_cli;
+pub mod alloc;
+pub mod aoc_client;
 pub mod commands;
+pub mod compare;
+pub mod counters;
+pub mod differential;
+pub mod generate;
+pub mod history;
+pub mod profiler;
 pub mod runner;
 
+pub use answer::*;
 pub use day::*;
+pub use params::*;
+pub use year::*;
 
+// Used by the `examples!` macro, not part of the public API.
+#[doc(hidden)]
+pub use paste as __paste;
+
+mod answer;
 mod day;
+mod fuzz;
+mod leaderboard;
+mod markdown;
+mod params;
+mod puzzle;
+mod readme;
 mod readme_benchmarks;
+mod readme_stars;
+mod report;
+mod run_inputs;
 mod run_multi;
+mod scale;
+mod submissions;
 mod timings;
+mod watch;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Returns the data directory, `./data` unless overridden by the `AOC_DATA_DIR` environment variable.
+#[must_use]
+pub fn data_dir() -> PathBuf {
+    match env::var_os("AOC_DATA_DIR") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => env::current_dir().unwrap().join("data"),
+    }
+}
+
+/// Returns the data directory of a year, e.g. `./data/2024`.
+#[must_use]
+pub fn year_dir(year: Year) -> PathBuf {
+    data_dir().join(year.to_string())
+}
+
+/// Returns the path of a solution binary, e.g. `./src/bin/2024-05.rs`.
+#[must_use]
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{}.rs", puzzle.bin_name())
+}
+
+fn read_path(filepath: &Path) -> String {
+    fs::read_to_string(filepath)
+        .unwrap_or_else(|e| panic!("could not open input file \"{}\": {e}", filepath.display()))
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
+    read_path(
+        &year_dir(puzzle.year)
+            .join(folder)
+            .join(format!("{}.txt", puzzle.day)),
+    )
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
+    read_path(
+        &year_dir(puzzle.year)
+            .join(folder)
+            .join(format!("{}-{part}.txt", puzzle.day)),
+    )
+}
+
+fn example_path(puzzle: PuzzleId, file: Option<u8>) -> PathBuf {
+    let name = match file {
+        Some(n) => format!("{}-{n}.txt", puzzle.day),
+        None => format!("{}.txt", puzzle.day),
+    };
+    year_dir(puzzle.year).join("examples").join(name)
+}
+
+// Not part of the public API, used by the `examples!` macro.
+#[doc(hidden)]
+pub fn __check_example<T: PartialEq + Debug>(
+    puzzle: PuzzleId,
+    file: Option<u8>,
+    part: u8,
+    func: impl Fn(&str) -> T,
+    expected: T,
+) {
+    let path = example_path(puzzle, file);
+    let result = func(&read_path(&path));
+    assert!(
+        result == expected,
+        "part {part} of example \"{}\": expected {expected:?}, got {result:?}",
+        path.display()
+    );
+}
+
+/// Where the input of a solution binary is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `data/<year>/inputs/NN.txt`, or `data/<year>/inputs/<profile>/NN.txt` if a profile is set.
+    Profile(Option<String>),
+    /// An explicit file passed with `--input PATH`.
+    File(PathBuf),
+    /// Standard input, selected with `--input -`.
+    Stdin,
+}
+
+impl InputSource {
+    /// Reads the input source from the command-line arguments of a solution binary.
+    /// `--input PATH` takes precedence over `--profile NAME` and the `AOC_PROFILE` environment variable.
+    pub fn from_args(args: &[String]) -> Self {
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1))
+                .cloned()
+        };
+
+        match value_of("--input") {
+            Some(path) if path == "-" => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+            None => InputSource::Profile(
+                value_of("--profile")
+                    .or_else(|| env::var("AOC_PROFILE").ok())
+                    .filter(|p| !p.is_empty()),
+            ),
+        }
+    }
+
+    /// Returns the path to read from, `None` for stdin.
+    pub fn path(&self, puzzle: PuzzleId) -> Option<PathBuf> {
+        match self {
+            InputSource::Profile(profile) => {
+                let mut dir = year_dir(puzzle.year).join("inputs");
+                if let Some(profile) = profile {
+                    dir.push(profile);
+                }
+                Some(dir.join(format!("{}.txt", puzzle.day)))
+            }
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Stdin => None,
+        }
+    }
+
+    /// Reads the input for `puzzle`.
+    pub fn read(&self, puzzle: PuzzleId) -> Result<String, String> {
+        let Some(path) = self.path(puzzle) else {
+            let mut input = String::new();
+            return io::stdin()
+                .read_to_string(&mut input)
+                .map(|_| input)
+                .map_err(|e| format!("could not read input from stdin: {e}"));
+        };
+
+        fs::read_to_string(&path).map_err(|e| {
+            let hint = match self {
+                InputSource::Profile(_) => {
+                    format!(" Try running `cargo download {puzzle}` to fetch it.")
+                }
+                _ => String::new(),
+            };
+            format!(
+                "could not open input file \"{}\": {e}.{hint}",
+                path.display()
+            )
+        })
+    }
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Reads the puzzle input of a solution binary based on its command-line arguments.
+/// Exits with an error message if the input can not be read.
+#[must_use]
+pub fn read_input(puzzle: PuzzleId) -> String {
+    let args: Vec<String> = env::args().collect();
+    InputSource::from_args(&args)
+        .read(puzzle)
+        .unwrap_or_else(|e| {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        })
+}
+
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The optional, third parameter (1 or 2) allows you to only run a single part of the solution.
+/// It can be followed by these options, in this order:
+///  - `parse = <fn>` parses the input once, timed separately. Both parts then take `&Parsed` instead of `&str`.
+///  - `params = <type>` passes the parameters of the day (see [`params!`](crate::params)) to each part,
+///    which then takes them as its second argument: `part_one(input: &str, params: &Params)`.
+///  - `generate = <fn>` generates random inputs for `cargo fuzz-day`, see [`Generator`](crate::template::generate::Generator).
+///  - `reference = <module>` names a module with naive `part_one` and `part_two` functions of the same signatures,
+///    which run instead of the solution when the binary is passed `--reference`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr $(, parse = $parse:path)? $(, params = $params:ty)? $(, generate = $generate:path)? $(, reference = $reference:ident)? $(,)?) => {
+        $crate::solution!(@impl $year, $day, [$($parse)?], [$($params)?], [$($generate)?], [$($reference)?], [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($year:expr, $day:expr, 1 $(, parse = $parse:path)? $(, params = $params:ty)? $(, generate = $generate:path)? $(, reference = $reference:ident)? $(,)?) => {
+        $crate::solution!(@impl $year, $day, [$($parse)?], [$($params)?], [$($generate)?], [$($reference)?], [part_one, 1]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($year:expr, $day:expr, 2 $(, parse = $parse:path)? $(, params = $params:ty)? $(, generate = $generate:path)? $(, reference = $reference:ident)? $(,)?) => {
+        $crate::solution!(@impl $year, $day, [$($parse)?], [$($params)?], [$($generate)?], [$($reference)?], [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $year:expr, $day:expr, [$($parse:path)?], [$($params:ty)?], [$($generate:path)?], [$($reference:ident)?], $( [$func:ident, $part:expr] )*) => {
+        /// The year of the puzzle.
+        const YEAR: $crate::template::Year = $crate::year!($year);
+
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The puzzle this binary solves.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new(YEAR, DAY);
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(feature = "alloc-stats", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let is_reference = $crate::template::generate::__handle_hook_args(
+                $crate::solution!(@generator $($generate)?),
+                $crate::solution!(@has_reference $($reference)?),
+            );
+            let input = $crate::template::read_input(PUZZLE);
+            $crate::solution!(@dispatch input, is_reference, [$($reference)?], [$($parse)?], [$($params)?], $( [$func, $part] )*);
         }
     };
+
+    (@generator) => {
+        None
+    };
+    (@generator $generate:path) => {
+        Some($generate as $crate::template::generate::Generator)
+    };
+
+    (@has_reference) => {
+        false
+    };
+    (@has_reference $reference:ident) => {
+        true
+    };
+
+    (@dispatch $input:ident, $is_reference:ident, [], $parse:tt, $params:tt, $( [$func:ident, $part:expr] )*) => {
+        let _ = $is_reference;
+        $crate::solution!(@run $input, $parse, $params, $( [$func, $part] )*);
+    };
+    (@dispatch $input:ident, $is_reference:ident, [$reference:ident], $parse:tt, $params:tt, $( [$func:ident, $part:expr] )*) => {
+        if $is_reference {
+            $crate::solution!(@run $input, $parse, $params, $( [$reference::$func, $part] )*);
+        } else {
+            $crate::solution!(@run $input, $parse, $params, $( [$func, $part] )*);
+        }
+    };
+
+    (@run $input:ident, [], [], $( [$func:expr, $part:expr] )*) => {
+        let outcomes = [$( run_part($func, &$input, PUZZLE, $part) ),*];
+        exit_on_failure(&outcomes);
+    };
+    (@run $input:ident, [], [$params:ty], $( [$func:expr, $part:expr] )*) => {
+        let (params, $input) = $crate::template::read_params::<$params>(&$input);
+        let outcomes = [$( run_part(|input: &str| $func(input, &params), $input, PUZZLE, $part) ),*];
+        exit_on_failure(&outcomes);
+    };
+    (@run $input:ident, [$parse:path], [], $( [$func:expr, $part:expr] )*) => {
+        let parsed = run_parse($parse, $input.as_str());
+        let outcomes = [$( run_part($func, &parsed, PUZZLE, $part) ),*];
+        exit_on_failure(&outcomes);
+    };
+    (@run $input:ident, [$parse:path], [$params:ty], $( [$func:expr, $part:expr] )*) => {
+        let (params, $input) = $crate::template::read_params::<$params>(&$input);
+        let parsed = run_parse($parse, $input);
+        let outcomes = [$( run_part(|parsed| $func(parsed, &params), &parsed, PUZZLE, $part) ),*];
+        exit_on_failure(&outcomes);
+    };
+}
+
+/// Generates a test per example file and part, checking `part_one` and `part_two` against the expected answers.
+/// Keys are the number of an example file (`NN-<n>.txt`), or `_` for the default example file (`NN.txt`).
+/// Parts that are `None` are not tested. Invoke it inside the `tests` module of a solution, `PUZZLE` needs to be in scope.
+/// For solutions with a parse stage or parameters, start with the same options as [`solution!`](crate::solution),
+/// e.g. `parse = parse_input, params = Params,`. The parameters are then read from the header of each example file.
+///
+/// ```ignore
+/// advent_of_code::examples! {
+///     1 => (Some(60), None),
+///     2 => (Some(1928), Some(2858)),
+/// }
+/// ```
+///
+/// This generates the tests `example_1_part_one`, `example_2_part_one` and `example_2_part_two`.
+#[macro_export]
+macro_rules! examples {
+    (parse = $parse:path, params = $params:ty, $($file:tt => ($($answers:tt)*)),* $(,)?) => {
+        $( $crate::examples!(@file [$parse] [$params] $file, $($answers)*); )*
+    };
+    (parse = $parse:path, $($file:tt => ($($answers:tt)*)),* $(,)?) => {
+        $( $crate::examples!(@file [$parse] [] $file, $($answers)*); )*
+    };
+    (params = $params:ty, $($file:tt => ($($answers:tt)*)),* $(,)?) => {
+        $( $crate::examples!(@file [] [$params] $file, $($answers)*); )*
+    };
+    ($($file:tt => ($($answers:tt)*)),* $(,)?) => {
+        $( $crate::examples!(@file [] [] $file, $($answers)*); )*
+    };
+
+    (@file $parse:tt $params:tt $file:tt, None, None $(,)?) => {};
+    (@file $parse:tt $params:tt $file:tt, None, $part_two:expr $(,)?) => {
+        $crate::examples!(@test $parse $params $file, part_two, 2, $part_two);
+    };
+    (@file $parse:tt $params:tt $file:tt, $part_one:expr, None $(,)?) => {
+        $crate::examples!(@test $parse $params $file, part_one, 1, $part_one);
+    };
+    (@file $parse:tt $params:tt $file:tt, $part_one:expr, $part_two:expr $(,)?) => {
+        $crate::examples!(@test $parse $params $file, part_one, 1, $part_one);
+        $crate::examples!(@test $parse $params $file, part_two, 2, $part_two);
+    };
+
+    (@test $parse:tt $params:tt _, $func:ident, $part:expr, $expected:expr) => {
+        $crate::template::__paste::paste! {
+            #[test]
+            fn [<example_ $func>]() {
+                $crate::template::__check_example(PUZZLE, None, $part, $crate::examples!(@func $parse $params $func), $expected);
+            }
+        }
+    };
+    (@test $parse:tt $params:tt $file:literal, $func:ident, $part:expr, $expected:expr) => {
+        $crate::template::__paste::paste! {
+            #[test]
+            fn [<example_ $file _ $func>]() {
+                $crate::template::__check_example(PUZZLE, Some($file), $part, $crate::examples!(@func $parse $params $func), $expected);
+            }
+        }
+    };
+
+    (@func [] [] $func:path) => {
+        $func
+    };
+    (@func [] [$params:ty] $func:path) => {
+        |input: &str| {
+            let (params, input) = $crate::examples!(@params $params, input);
+            $func(input, &params)
+        }
+    };
+    (@func [$parse:path] [] $func:path) => {
+        |input: &str| $func(&$parse(input))
+    };
+    (@func [$parse:path] [$params:ty] $func:path) => {
+        |input: &str| {
+            let (params, input) = $crate::examples!(@params $params, input);
+            $func(&$parse(input), &params)
+        }
+    };
+
+    (@params $params:ty, $input:ident) => {
+        $crate::template::parse_params::<$params, &str>($input, &[])
+            .unwrap_or_else(|e| panic!("invalid example header: {e}"))
+    };
+}
+
+/// Generates tests comparing `part_one` and `part_two` with the naive functions of the `reference` module of a solution.
+/// Both run on every example file of the day, and on generated inputs if a generator is given.
+/// Inputs the reference does not answer are skipped. A difference on a generated input is shrunk to the smallest one.
+/// Invoke it inside the `tests` module of a solution, with the same options as [`solution!`](crate::solution).
+///
+/// ```ignore
+/// advent_of_code::differential!(generate = generate);
+/// ```
+///
+/// This generates the tests `reference_matches_examples_part_one`, `reference_matches_generated_part_one`
+/// and the same for `part_two`.
+#[macro_export]
+macro_rules! differential {
+    ($(parse = $parse:path,)? $(params = $params:ty,)? $(generate = $generate:path)? $(,)?) => {
+        $crate::differential!(@test [$($parse)?] [$($params)?] [$($generate)?] part_one, 1);
+        $crate::differential!(@test [$($parse)?] [$($params)?] [$($generate)?] part_two, 2);
+    };
+
+    (@test $parse:tt $params:tt [$($generate:path)?] $func:ident, $part:expr) => {
+        $crate::template::__paste::paste! {
+            #[test]
+            fn [<reference_matches_examples_ $func>]() {
+                $crate::template::differential::__check_examples(
+                    PUZZLE,
+                    $part,
+                    $crate::examples!(@func $parse $params $func),
+                    $crate::examples!(@func $parse $params reference::$func),
+                );
+            }
+
+            $(
+                #[test]
+                fn [<reference_matches_generated_ $func>]() {
+                    $crate::template::differential::__check_generated(
+                        $part,
+                        $generate,
+                        $crate::examples!(@func $parse $params $func),
+                        $crate::examples!(@func $parse $params reference::$func),
+                    );
+                }
+            )?
+        }
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{InputSource, PuzzleId};
+    use std::path::PathBuf;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn reads_input_flag() {
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--input", "foo.txt"])),
+            InputSource::File(PathBuf::from("foo.txt"))
+        );
+        assert_eq!(
+            InputSource::from_args(&args(&["01", "--input", "-", "--profile", "alice"])),
+            InputSource::Stdin
+        );
+    }
+
+    #[test]
+    fn reads_profile_flag() {
+        let source = InputSource::from_args(&args(&["01", "--profile", "alice"]));
+        assert_eq!(source, InputSource::Profile(Some("alice".into())));
+        assert!(source
+            .path(PuzzleId::new(crate::year!(2023), crate::day!(5)))
+            .unwrap()
+            .ends_with("2023/inputs/alice/05.txt"));
+    }
+
+    mod examples_macro {
+        use crate::template::PuzzleId;
+        use crate::{day, year};
+
+        const PUZZLE: PuzzleId = PuzzleId::new(year!(2024), day!(1));
+
+        fn part_one(input: &str) -> Option<usize> {
+            Some(input.lines().count())
+        }
+
+        // checks that parts without an expected answer are not tested, so it is never called.
+        #[allow(dead_code)]
+        fn part_two(_input: &str) -> Option<usize> {
+            unreachable!("parts without an expected answer are not tested")
+        }
+
+        crate::examples! {
+            _ => (Some(6), None),
+        }
+    }
+
+    mod examples_macro_with_parse {
+        use crate::template::PuzzleId;
+        use crate::{day, year};
+
+        const PUZZLE: PuzzleId = PuzzleId::new(year!(2024), day!(1));
+
+        fn parse(input: &str) -> Vec<u32> {
+            input
+                .split_whitespace()
+                .map(|n| n.parse().unwrap())
+                .collect()
+        }
+
+        fn part_one(numbers: &[u32]) -> Option<u32> {
+            numbers.iter().max().copied()
+        }
+
+        fn part_two(numbers: &[u32]) -> Option<usize> {
+            Some(numbers.len())
+        }
+
+        crate::examples! {
+            parse = parse,
+            _ => (Some(9), Some(12)),
+        }
+    }
 }
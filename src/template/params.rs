@@ -0,0 +1,198 @@
+/// Typed puzzle parameters, for values that differ between the real input and the examples (e.g. the size of a grid).
+/// Parameters are overridden by a header at the top of an input and by `--param key=value` on the command line.
+use std::{env, process};
+
+/// Prefix of the header lines of an input, e.g. `#! width=7 height=7`.
+const HEADER_PREFIX: &str = "#!";
+
+/// A `key=value` pair of an input header or the command line.
+pub type Assignment<'a> = (&'a str, &'a str);
+
+/// Parameters of a solution. Implement it with the [`params!`](crate::params) macro.
+pub trait Params: Default {
+    /// Sets the parameter `key` from its textual value.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+}
+
+fn split_assignment(assignment: &str) -> Result<Assignment<'_>, String> {
+    assignment
+        .split_once('=')
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .filter(|(key, _)| !key.is_empty())
+        .ok_or_else(|| format!("expecting `key=value`, got `{assignment}`"))
+}
+
+/// Splits the parameter header off an input. Returns the assignments of the header and the remaining input.
+/// The header consists of the leading lines starting with `#!`, each holding one or more `key=value` pairs.
+pub fn split_header(input: &str) -> Result<(Vec<Assignment<'_>>, &str), String> {
+    let mut assignments = vec![];
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        let Some(header) = line.strip_prefix(HEADER_PREFIX) else {
+            break;
+        };
+        for assignment in header.split_whitespace() {
+            assignments.push(split_assignment(assignment)?);
+        }
+        offset += line.len();
+    }
+
+    Ok((assignments, &input[offset..]))
+}
+
+/// Parses the parameters of `input`: defaults, overridden by the header of the input and then by `overrides`.
+/// Returns the parameters and the input without its header.
+pub fn parse_params<'a, P: Params, S: AsRef<str>>(
+    input: &'a str,
+    overrides: &[S],
+) -> Result<(P, &'a str), String> {
+    let (header, input) = split_header(input)?;
+    let mut params = P::default();
+
+    for (key, value) in header {
+        params.set(key, value)?;
+    }
+    for assignment in overrides {
+        let (key, value) = split_assignment(assignment.as_ref())?;
+        params.set(key, value)?;
+    }
+
+    Ok((params, input))
+}
+
+/// Reads the parameters of a solution binary from its input and the `--param` command-line arguments.
+/// Exits with an error message if a parameter is invalid.
+#[must_use]
+pub fn read_params<P: Params>(input: &str) -> (P, &str) {
+    let args: Vec<String> = env::args().collect();
+    let overrides: Vec<&String> = args
+        .windows(2)
+        .filter(|w| w[0] == "--param")
+        .map(|w| &w[1])
+        .collect();
+
+    parse_params(input, &overrides).unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Declares the parameters of a solution and their defaults for the real input.
+///
+/// ```
+/// advent_of_code::params! {
+///     #[derive(Debug)]
+///     pub struct Params {
+///         width: usize = 71,
+///         height: usize = 71,
+///     }
+/// }
+///
+/// # use advent_of_code::template::{parse_params, Params as _};
+/// let (params, input) = parse_params::<Params, &str>("#! width=7\n5,4\n", &["height=9"]).unwrap();
+/// assert_eq!((params.width, params.height, input), (7, 9, "5,4\n"));
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $( $(#[$field_meta:meta])* $field:ident : $ty:ty = $default:expr ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $( $(#[$field_meta])* $field: $ty, )*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self { $( $field: $default, )* }
+            }
+        }
+
+        impl $crate::template::Params for $name {
+            fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+                match key {
+                    $(
+                        stringify!($field) => {
+                            self.$field = value
+                                .parse()
+                                .map_err(|_| format!("invalid value `{value}` for parameter `{key}`"))?;
+                        }
+                    )*
+                    _ => {
+                        let known: &[&str] = &[$( stringify!($field) ),*];
+                        return Err(format!(
+                            "unknown parameter `{key}`, expecting one of: {}",
+                            known.join(", ")
+                        ));
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_params, split_header};
+
+    crate::params! {
+        #[derive(Debug, PartialEq)]
+        struct Grid {
+            width: usize = 71,
+            height: usize = 71,
+        }
+    }
+
+    #[test]
+    fn splits_header() {
+        assert_eq!(
+            split_header("#! width=7 height=7\n#! bytes = 12\n5,4\n"),
+            Err("expecting `key=value`, got `bytes`".into())
+        );
+        assert_eq!(
+            split_header("#! width=7 height=7\n#! bytes=12\n5,4\n#! x=1\n"),
+            Ok((
+                vec![("width", "7"), ("height", "7"), ("bytes", "12")],
+                "5,4\n#! x=1\n"
+            ))
+        );
+        assert_eq!(split_header("#.#\n"), Ok((vec![], "#.#\n")));
+    }
+
+    #[test]
+    fn overrides_defaults() {
+        let (params, input) = parse_params::<Grid, &str>("#! width=7\n5,4", &[]).unwrap();
+        assert_eq!(
+            params,
+            Grid {
+                width: 7,
+                height: 71
+            }
+        );
+        assert_eq!(input, "5,4");
+
+        let (params, _) = parse_params::<Grid, &str>("#! width=7\n", &["width=9"]).unwrap();
+        assert_eq!(params.width, 9);
+    }
+
+    #[test]
+    fn rejects_invalid_params() {
+        assert_eq!(
+            parse_params::<Grid, &str>("", &["depth=1"]).unwrap_err(),
+            "unknown parameter `depth`, expecting one of: width, height"
+        );
+        assert_eq!(
+            parse_params::<Grid, &str>("#! width=abc\n", &[]).unwrap_err(),
+            "invalid value `abc` for parameter `width`"
+        );
+    }
+}
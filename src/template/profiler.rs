@@ -0,0 +1,284 @@
+/// A sampling profiler for a single part of a solution, used by `cargo flame <day> --part <n>`.
+/// Call stacks are sampled on `SIGPROF` and written as folded stacks and an SVG flame graph to `target/flame/`.
+/// Only the solver is sampled: the input is parsed before the profiler starts, and it is paused while the input is cloned.
+/// Threads of rayon are sampled as well, so parts that run in parallel show the work of every thread.
+/// Sampling needs the `profile` feature and is only available on Linux.
+use std::{collections::BTreeMap, env, path::PathBuf};
+
+use crate::template::PuzzleId;
+
+/// Passed to a solution binary to profile one of its parts.
+pub const FLAME_ARG: &str = "--flame";
+
+/// Frames up to and including this function belong to the runner and are cut off.
+const ROOT_FRAME: &str = "profiler::sampler::run_batch";
+
+/// Stacks of other threads are kept from their first frame of rayon on.
+const RAYON_FRAME: &str = "rayon_core::";
+
+/// Number of samples of every folded stack.
+pub type Stacks = BTreeMap<String, usize>;
+
+/// Reads the part to profile from the `--flame` argument of a solution binary.
+pub fn flame_part() -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+    args.windows(2)
+        .find(|w| w[0] == FLAME_ARG)
+        .and_then(|w| w[1].parse().ok())
+}
+
+/// Folds a call stack, given from the root to the leaf, into a line of `root;...;leaf`.
+/// The frames of the runner are replaced by `root`, stacks of rayon's worker threads start at their first rayon frame.
+/// Returns `None` for samples taken outside of the solver.
+pub fn fold_stack<S: AsRef<str>>(root: &str, frames: &[S]) -> Option<String> {
+    let start = match frames
+        .iter()
+        .rposition(|frame| frame.as_ref().contains(ROOT_FRAME))
+    {
+        Some(pos) => pos + 1,
+        None => frames
+            .iter()
+            .position(|frame| frame.as_ref().contains(RAYON_FRAME))?,
+    };
+
+    let mut stack = vec![root];
+    stack.extend(frames[start..].iter().map(AsRef::as_ref));
+    Some(stack.join(";"))
+}
+
+/// Formats stacks in the folded format of `inferno` and `flamegraph.pl`, e.g. `Part 1;part_one;parse 12`.
+pub fn folded_lines(stacks: &Stacks) -> Vec<String> {
+    stacks
+        .iter()
+        .map(|(stack, samples)| format!("{stack} {samples}"))
+        .collect()
+}
+
+/// Path of the output files of a part, without extension, e.g. `target/flame/2024-05-part1`.
+pub fn output_path(puzzle: PuzzleId, part: u8) -> PathBuf {
+    PathBuf::from("target")
+        .join("flame")
+        .join(format!("{}-part{part}", puzzle.bin_name()))
+}
+
+#[cfg(all(feature = "profile", target_os = "linux"))]
+pub use sampler::profile;
+
+#[cfg(not(all(feature = "profile", target_os = "linux")))]
+pub fn profile<I: Clone, R: crate::template::PartResult>(
+    _func: impl Fn(I) -> R,
+    _input: I,
+    _puzzle: PuzzleId,
+    _part: u8,
+) -> crate::template::Outcome {
+    eprintln!(
+        "Error: the built-in profiler needs the `profile` feature and is only available on Linux."
+    );
+    std::process::exit(1);
+}
+
+#[cfg(all(feature = "profile", target_os = "linux"))]
+mod sampler {
+    use std::fs;
+    use std::hint::black_box;
+    use std::io::{stdout, Write};
+    use std::panic::{self, AssertUnwindSafe};
+    use std::process;
+    use std::time::{Duration, Instant};
+
+    use pprof::{ProfilerGuardBuilder, Report};
+
+    use super::{fold_stack, folded_lines, output_path, Stacks};
+    use crate::template::answer::panic_message;
+    use crate::template::runner::print_result;
+    use crate::template::{Outcome, PartResult, PuzzleId, ANSI_ITALIC, ANSI_RESET};
+
+    /// Samples per second.
+    const FREQUENCY: i32 = 1000;
+
+    /// Approximate time spent running the part while sampling.
+    const DURATION: Duration = Duration::from_secs(5);
+
+    const MAX_RUNS: u128 = 100_000;
+
+    /// Inputs are cloned in batches of this size, so their clones are not kept in memory all at once.
+    const BATCH_SIZE: usize = 100;
+
+    /// Shared libraries that are not sampled, as unwinding through them is unreliable.
+    const BLOCKLIST: [&str; 4] = ["libc", "libgcc", "pthread", "vdso"];
+
+    /// Runs the part once to get its answer, then samples repeated runs of it for about [`DURATION`].
+    pub fn profile<I: Clone, R: PartResult>(
+        func: impl Fn(I) -> R,
+        input: I,
+        puzzle: PuzzleId,
+        part: u8,
+    ) -> Outcome {
+        let part_str = format!("Part {part}");
+
+        let timer = Instant::now();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| func(input.clone())));
+        let base_time = timer.elapsed();
+
+        let outcome = match result {
+            Ok(result) => result.into_outcome(),
+            Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
+        };
+
+        if !outcome.is_solved() {
+            print_result(&outcome, &part_str, " (not profiled)");
+            return outcome;
+        }
+
+        let runs = (DURATION.as_nanos() / base_time.as_nanos().max(1)).clamp(1, MAX_RUNS);
+        print_result(&outcome, &part_str, "");
+        print!(" > {ANSI_ITALIC}profiling {runs} runs{ANSI_RESET}");
+        let _ = stdout().flush();
+
+        let mut stacks = Stacks::new();
+        #[allow(clippy::cast_possible_truncation)]
+        let mut remaining = runs as usize;
+        while remaining > 0 {
+            let batch: Vec<I> = (0..remaining.min(BATCH_SIZE))
+                .map(|_| input.clone())
+                .collect();
+            remaining -= batch.len();
+
+            // the profiler only runs while the batch does, so cloning is not sampled on any thread.
+            let report = sample(|| run_batch(&func, batch)).unwrap_or_else(|e| {
+                eprintln!("\nError: failed to sample part {part}: {e}");
+                process::exit(1);
+            });
+            fold_report(&report, &part_str, &mut stacks);
+        }
+
+        print_result(&outcome, &part_str, &format!(" ({runs} runs)"));
+        write_outputs(&stacks, puzzle, part);
+        outcome
+    }
+
+    fn sample(run: impl FnOnce()) -> pprof::Result<Report> {
+        let guard = ProfilerGuardBuilder::default()
+            .frequency(FREQUENCY)
+            .blocklist(&BLOCKLIST)
+            .build()?;
+        run();
+        guard.report().build()
+    }
+
+    /// Runs `func` on every input of the batch. Samples are cut off at this frame, see [`fold_stack`].
+    #[inline(never)]
+    fn run_batch<I, R>(func: &impl Fn(I) -> R, batch: Vec<I>) {
+        for input in batch {
+            black_box(func(black_box(input)));
+        }
+    }
+
+    /// Adds the samples of `report` to `stacks`.
+    #[allow(clippy::cast_sign_loss)]
+    fn fold_report(report: &Report, root: &str, stacks: &mut Stacks) {
+        for (frames, samples) in &report.data {
+            // pprof lists frames from the leaf to the root, including inlined functions.
+            let names: Vec<String> = frames
+                .frames
+                .iter()
+                .rev()
+                .flat_map(|frame| frame.iter().rev().map(pprof::Symbol::name))
+                .collect();
+
+            if let Some(stack) = fold_stack(root, &names) {
+                *stacks.entry(stack).or_default() += (*samples).max(0) as usize;
+            }
+        }
+    }
+
+    fn write_outputs(stacks: &Stacks, puzzle: PuzzleId, part: u8) {
+        if stacks.is_empty() {
+            eprintln!("No samples were taken, the part ran too briefly to be profiled.");
+            return;
+        }
+
+        let path = output_path(puzzle, part);
+        let folded_path = path.with_extension("folded");
+        let svg_path = path.with_extension("svg");
+        let lines = folded_lines(stacks);
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&folded_path, lines.join("\n") + "\n"))
+            .map_err(|e| e.to_string())
+            .and_then(|()| {
+                let mut options = inferno::flamegraph::Options::default();
+                options.title = format!("{puzzle} part {part}");
+                options.count_name = "samples".into();
+
+                let file = fs::File::create(&svg_path).map_err(|e| e.to_string())?;
+                inferno::flamegraph::from_lines(
+                    &mut options,
+                    lines.iter().map(String::as_str),
+                    file,
+                )
+                .map_err(|e| e.to_string())
+            });
+
+        match result {
+            Ok(()) => println!(
+                "Wrote {} samples to \"{}\" and \"{}\".",
+                stacks.values().sum::<usize>(),
+                svg_path.display(),
+                folded_path.display()
+            ),
+            Err(e) => {
+                eprintln!("Error: failed to write the flame graph: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fold_stack, folded_lines, Stacks};
+
+    #[test]
+    fn folds_stacks() {
+        let frames = [
+            "main",
+            "advent_of_code::template::runner::run_part",
+            "advent_of_code::template::profiler::sampler::run_batch",
+            "day_05::part_one",
+            "core::slice::sort",
+        ];
+        assert_eq!(
+            fold_stack("Part 1", &frames).as_deref(),
+            Some("Part 1;day_05::part_one;core::slice::sort")
+        );
+        assert_eq!(fold_stack("Part 1", &frames[..2]), None);
+    }
+
+    #[test]
+    fn folds_stacks_of_rayon_workers() {
+        let frames = [
+            "std::sys::pal::unix::thread::Thread::new::thread_start",
+            "rayon_core::registry::ThreadBuilder::run",
+            "rayon_core::registry::WorkerThread::wait_until_cold",
+            "rayon_core::join::join_context::{{closure}}",
+            "day_22::part_two::{{closure}}",
+            "day_22::next_secret",
+        ];
+        assert_eq!(
+            fold_stack("Part 2", &frames).as_deref(),
+            Some("Part 2;rayon_core::registry::ThreadBuilder::run;rayon_core::registry::WorkerThread::wait_until_cold;rayon_core::join::join_context::{{closure}};day_22::part_two::{{closure}};day_22::next_secret")
+        );
+        assert_eq!(fold_stack("Part 2", &frames[..1]), None);
+    }
+
+    #[test]
+    fn formats_folded_lines() {
+        let stacks = Stacks::from([("Part 1;a".into(), 3), ("Part 1;a;b".into(), 7)]);
+        assert_eq!(folded_lines(&stacks), vec!["Part 1;a 3", "Part 1;a;b 7"]);
+    }
+}
@@ -0,0 +1,157 @@
+/// Helpers that extract information from the puzzle descriptions stored in `data/<year>/puzzles/NN.md`.
+use std::{fs, io};
+
+use crate::template::{year_dir, PuzzleId};
+
+const PART_TWO_HEADING: &str = "## --- Part Two ---";
+
+/// Reads the stored puzzle description of a day.
+pub fn read_puzzle(puzzle: PuzzleId) -> Result<String, io::Error> {
+    fs::read_to_string(
+        year_dir(puzzle.year)
+            .join("puzzles")
+            .join(format!("{}.md", puzzle.day)),
+    )
+}
+
+/// Returns the title of a puzzle, e.g. `Historian Hysteria` for `## --- Day 1: Historian Hysteria ---`.
+pub fn title(markdown: &str) -> Option<String> {
+    let heading = markdown
+        .lines()
+        .find(|l| l.starts_with("## --- Day "))?
+        .trim_start_matches("## --- ")
+        .trim_end_matches(" ---");
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim().to_string())
+}
+
+/// Splits a puzzle description into the text of part one and part two (if unlocked).
+pub fn parts(markdown: &str) -> (&str, Option<&str>) {
+    match markdown.find(PART_TWO_HEADING) {
+        Some(pos) => (&markdown[..pos], Some(&markdown[pos..])),
+        None => (markdown, None),
+    }
+}
+
+/// Returns the contents of every fenced code block that spans more than one line.
+/// Single-line blocks are usually inline values rather than example inputs.
+/// Duplicates are removed, keeping the first occurrence.
+pub fn example_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks: Vec<String> = vec![];
+    for block in code_blocks(markdown) {
+        if !blocks.contains(&block) {
+            blocks.push(block);
+        }
+    }
+    blocks
+}
+
+/// Returns the contents of every fenced code block that spans more than one line, in order.
+fn code_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks: Vec<String> = vec![];
+    let mut current: Option<Vec<&str>> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match current.take() {
+                Some(lines) if lines.len() > 1 => blocks.push(lines.join("\n") + "\n"),
+                Some(_) => {}
+                None => current = Some(vec![]),
+            }
+        } else if let Some(lines) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+
+    blocks
+}
+
+/// Returns the expected example answer of a part.
+/// Advent of Code highlights the answer as emphasized code, the last highlighted value of a part is assumed to be it.
+pub fn expected_answer(part_text: &str) -> Option<String> {
+    let mut answer = None;
+    let mut rest = part_text;
+
+    while let Some(start) = rest.find("`*") {
+        let value_start = start + 2;
+        let Some(len) = rest[value_start..].find("*`") else {
+            break;
+        };
+        let value = &rest[value_start..value_start + len];
+        if !value.is_empty() && !value.contains('\n') {
+            answer = Some(value.to_string());
+        }
+        rest = &rest[value_start + len + 2..];
+    }
+
+    answer
+}
+
+/// Returns the number of the example file an answer belongs to, i.e. the last example block before the answer.
+/// `before_answer` is the puzzle description up to the answer, `blocks` the example blocks of the whole description.
+pub fn answer_example(before_answer: &str, blocks: &[String]) -> Option<u8> {
+    let block = code_blocks(before_answer).pop()?;
+    let index = blocks.iter().position(|b| *b == block)?;
+    u8::try_from(index + 1).ok()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answer_example, example_blocks, expected_answer, parts, title};
+
+    const PUZZLE: &str = "## --- Day 1: Historian Hysteria ---
+
+For example:
+
+```
+3   4
+4   3
+```
+
+Sum the `*distance*`. In the example above, this is `*11*`.
+
+```
+single line
+```
+
+## --- Part Two ---
+
+Using the same example:
+
+```
+3   4
+4   3
+```
+
+So, the similarity score is `*31*`.";
+
+    #[test]
+    fn extracts_title() {
+        assert_eq!(title(PUZZLE).as_deref(), Some("Historian Hysteria"));
+        assert_eq!(title("nothing"), None);
+    }
+
+    #[test]
+    fn extracts_example_blocks() {
+        assert_eq!(example_blocks(PUZZLE), vec!["3   4\n4   3\n".to_string()]);
+    }
+
+    #[test]
+    fn extracts_expected_answers() {
+        let (part_one, part_two) = parts(PUZZLE);
+        assert_eq!(expected_answer(part_one).as_deref(), Some("11"));
+        assert_eq!(expected_answer(part_two.unwrap()).as_deref(), Some("31"));
+    }
+
+    #[test]
+    fn finds_example_of_answer() {
+        let blocks = ["a\nb\n".to_string(), "c\nd\n".to_string()];
+        let puzzle = "```\na\nb\n```\n`*1*`\n```\nc\nd\n```\n`*2*`\n```\na\nb\n```\n`*3*`";
+
+        let before = |answer: &str| &puzzle[..puzzle.find(answer).unwrap()];
+        assert_eq!(answer_example(before("`*1*`"), &blocks), Some(1));
+        assert_eq!(answer_example(before("`*2*`"), &blocks), Some(2));
+        assert_eq!(answer_example(before("`*3*`"), &blocks), Some(1));
+        assert_eq!(answer_example("no blocks", &blocks), None);
+    }
+}
@@ -0,0 +1,161 @@
+/// Maintains generated sections of the readme, e.g. the benchmark and star tables.
+/// The approach taken is similar to how `aoc-readme-stars` handles this.
+/// Every year gets its own section, delimited by a pair of `<!--- <name> <year> --->` markers.
+use std::{fs, io};
+
+use crate::template::Year;
+
+const README_PATH: &str = "README.md";
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+pub struct TablePosition {
+    pos_start: usize,
+    pos_end: usize,
+}
+
+fn marker(marker_prefix: &str, year: Year) -> String {
+    format!("{marker_prefix} {year} --->")
+}
+
+/// Returns the byte range of every marker starting with `marker_prefix`.
+/// Markers quoted as inline code, e.g. in the documentation, are not matched.
+fn markers(readme: &str, marker_prefix: &str) -> Vec<(usize, usize)> {
+    readme
+        .match_indices(marker_prefix)
+        .map(|(start, _)| start)
+        .filter(|&start| {
+            let line_start = readme[..start].rfind('\n').map_or(0, |pos| pos + 1);
+            readme[line_start..start]
+                .matches('`')
+                .count()
+                .is_multiple_of(2)
+        })
+        .filter_map(|start| {
+            let len = readme[start..].find("--->")? + "--->".len();
+            Some((start, start + len))
+        })
+        .collect()
+}
+
+/// Locates the section of `year`. Returns `None` if the readme has no section for this year yet.
+fn locate_section(
+    readme: &str,
+    marker_prefix: &str,
+    year: Year,
+) -> Result<Option<TablePosition>, Error> {
+    let marker = marker(marker_prefix, year);
+    let matches: Vec<_> = markers(readme, marker_prefix)
+        .into_iter()
+        .filter(|&(start, end)| readme[start..end] == marker)
+        .collect();
+
+    if matches.is_empty() {
+        return Ok(None);
+    }
+
+    if matches.len() != 2 {
+        return Err(Error::Parser(format!(
+            "{marker}: expected exactly two occurences of marker in README."
+        )));
+    }
+
+    Ok(Some(TablePosition {
+        pos_start: matches[0].0,
+        pos_end: matches[1].1,
+    }))
+}
+
+/// Replaces the section of `year` with `lines`, wrapped in its markers.
+/// A section for a new year is added after the last existing marker starting with `marker_prefix`.
+pub fn update_section(
+    s: &mut String,
+    marker_prefix: &str,
+    year: Year,
+    lines: Vec<String>,
+) -> Result<(), Error> {
+    let marker = marker(marker_prefix, year);
+    let mut section = vec![marker.clone()];
+    section.extend(lines);
+    section.push(marker);
+    let section = section.join("\n");
+
+    if let Some(positions) = locate_section(s, marker_prefix, year)? {
+        s.replace_range(positions.pos_start..positions.pos_end, &section);
+        return Ok(());
+    }
+
+    let (_, insert_at) = markers(s, marker_prefix)
+        .pop()
+        .ok_or_else(|| Error::Parser(format!("Could not find a `{marker_prefix}` marker.")))?;
+
+    s.insert_str(insert_at, &format!("\n\n{section}"));
+    Ok(())
+}
+
+/// Applies `update` to the readme and writes it back.
+pub fn update_readme(update: impl FnOnce(&mut String) -> Result<(), Error>) -> Result<(), Error> {
+    let mut readme = String::from_utf8_lossy(&fs::read(README_PATH)?).to_string();
+    update(&mut readme)?;
+    fs::write(README_PATH, &readme)?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::update_section;
+    use crate::year;
+
+    const PREFIX: &str = "<!--- benchmarking table";
+
+    const README: &str = "# Title
+
+<!--- benchmarking table 2024 --->
+old table
+<!--- benchmarking table 2024 --->
+
+## Docs
+
+Tables are delimited by `<!--- benchmarking table <year> --->` markers.
+";
+
+    #[test]
+    fn replaces_existing_section() {
+        let mut s = README.to_string();
+        update_section(&mut s, PREFIX, year!(2024), vec!["new table".into()]).unwrap();
+        assert!(s.contains("<!--- benchmarking table 2024 --->\nnew table\n<!--- benchmarking table 2024 --->\n\n## Docs"));
+        assert!(!s.contains("old table"));
+    }
+
+    #[test]
+    fn appends_new_year_below_last_marker() {
+        let mut s = README.to_string();
+        update_section(&mut s, PREFIX, year!(2025), vec!["2025 table".into()]).unwrap();
+        assert!(s.contains("old table\n<!--- benchmarking table 2024 --->\n\n<!--- benchmarking table 2025 --->\n2025 table\n<!--- benchmarking table 2025 --->\n\n## Docs"));
+        assert!(s.ends_with("`<!--- benchmarking table <year> --->` markers.\n"));
+    }
+
+    #[test]
+    fn ignores_markers_quoted_in_prose() {
+        let mut s = "<!--- advent_readme_stars table --->\n\nPlaced below the `<!--- advent_readme_stars table <year> --->` marker.\n".to_string();
+        update_section(
+            &mut s,
+            "<!--- advent_readme_stars table",
+            year!(2024),
+            vec!["stars".into()],
+        )
+        .unwrap();
+        assert!(s.starts_with("<!--- advent_readme_stars table --->\n\n<!--- advent_readme_stars table 2024 --->\nstars\n<!--- advent_readme_stars table 2024 --->\n\nPlaced below"));
+    }
+}
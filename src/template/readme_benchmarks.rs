@@ -1,183 +1,156 @@
 /// Module that updates the readme me with timing information.
-/// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+/// Every year gets its own table, delimited by `<!--- benchmarking table <year> --->` markers.
+use std::fs;
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::readme::{update_readme, update_section, Error};
+use crate::template::report::{chart_path, Report};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+static MARKER_PREFIX: &str = "<!--- benchmarking table";
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
+/// Replaces the table of the report's year. A table for a new year is added after the last existing table.
+fn update_content(s: &mut String, report: &Report) -> Result<(), Error> {
+    update_section(s, MARKER_PREFIX, report.year, report.markdown("##"))
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
-}
+/// Updates the table of the report's year in the readme and renders its chart to `.assets/`.
+pub fn update(report: &Report) -> Result<(), Error> {
+    update_readme(|readme| update_content(readme, report))?;
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
-}
-
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
-
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+    let chart = chart_path(report.year);
+    if let Some(dir) = chart.parent() {
+        fs::create_dir_all(dir)?;
     }
-
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
-
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
-
-    Ok(TablePosition { pos_start, pos_end })
-}
-
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
-
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
-    }
-
-    lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
-
-    lines.join("\n")
-}
-
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
-}
-
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+    fs::write(chart, report.svg())?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::update_content;
+    use crate::template::report::Report;
+    use crate::template::Year;
+    use crate::{day, template::timings::Timing, template::timings::Timings, year};
+
+    const MARKER: &str = "<!--- benchmarking table 2024 --->";
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1: Some(1e+7),
+                    part_2: Some(2e+7),
+                    total_nanos: 3e+7,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1: Some(3e+7),
+                    part_2: Some(4e+7),
+                    total_nanos: 7e+7,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    part_1: Some(4e+7),
+                    part_2: Some(5e+7),
+                    total_nanos: 9e+7,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
     }
 
+    fn get_mock_report(year: Year) -> Report {
+        Report::new(year, get_mock_timings())
+    }
+
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_report(year!(2024))).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_report(year!(2024))).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(&mut s, &get_mock_report(year!(2024))).unwrap();
+        assert_eq!(s.contains("## 2024 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_report(year!(2024))).unwrap();
+        update_content(&mut s, &get_mock_report(year!(2024))).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        assert_eq!(
+            s.matches("## 2024 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_report(year!(2024))).unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2024 --->",
+            "## 2024 Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Part 1 | Part 2 | Share |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/2024-01.rs) | `10.0ms` | `20.0ms` | 15.8% |",
+            "| [Day 2](./src/bin/2024-02.rs) | `30.0ms` | `40.0ms` | 36.8% |",
+            "| [Day 4](./src/bin/2024-04.rs) | `40.0ms` | `50.0ms` | 47.4% |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "",
+            "![2024 benchmarks](./.assets/benchmarks-2024.svg)",
+            "<!--- benchmarking table 2024 --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn appends_table_for_new_year() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, &get_mock_report(year!(2023))).unwrap();
+        let old = s.rfind(MARKER).unwrap();
+        let new = s.find("## 2023 Benchmarks").unwrap();
+        assert!(old < new && new < s.find("baz").unwrap());
+        assert_eq!(s.matches("<!--- benchmarking table 2023 --->").count(), 2);
+        assert!(s.contains("./src/bin/2023-01.rs"));
+    }
+
+    #[test]
+    fn skips_days_outside_of_event() {
+        let mut timings = get_mock_timings();
+        timings.data[2].day = day!(13);
+        let marker = "<!--- benchmarking table 2025 --->";
+        let mut s = format!("{marker}{marker}");
+        update_content(&mut s, &Report::new(year!(2025), timings)).unwrap();
+        assert!(s.contains("[Day 2]"));
+        assert!(!s.contains("[Day 13]"));
+    }
+
+    #[test]
+    fn adds_parse_column() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some(5e+6);
+        let mut s = format!("{MARKER}{MARKER}");
+        update_content(&mut s, &Report::new(year!(2024), timings)).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/2024-01.rs) | `-` | `10.0ms` | `20.0ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2024-02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |"));
+    }
 }
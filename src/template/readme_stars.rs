@@ -0,0 +1,250 @@
+/// Module that updates the readme with the stars collected on every day.
+/// Stars are read from the correct answers in the submission ledger and, for offline use,
+/// from a private leaderboard saved to `data/<year>/leaderboard.json`.
+/// Every year gets its own table, delimited by `<!--- advent_readme_stars table <year> --->` markers.
+use std::{collections::BTreeSet, env, fs, io, path::PathBuf};
+
+use crate::template::leaderboard::Leaderboard;
+use crate::template::readme::{update_readme, update_section, Error};
+use crate::template::submissions::{Ledger, Verdict};
+use crate::template::{all_days, get_path_for_bin, year_dir, Day, PuzzleId, Year};
+
+static MARKER_PREFIX: &str = "<!--- advent_readme_stars table";
+
+const LEADERBOARD_FILE_NAME: &str = "leaderboard.json";
+
+/// Selects the member of a leaderboard whose stars are shown.
+const USER_ID_ENV: &str = "AOC_USER_ID";
+
+const STAR: &str = "⭐";
+
+fn leaderboard_path(year: Year) -> PathBuf {
+    year_dir(year).join(LEADERBOARD_FILE_NAME)
+}
+
+/// The parts solved in one year.
+#[derive(Clone, Debug)]
+pub struct Stars {
+    pub year: Year,
+    pub solved: BTreeSet<(Day, u8)>,
+}
+
+impl Stars {
+    pub fn new(year: Year) -> Self {
+        Stars {
+            year,
+            solved: BTreeSet::new(),
+        }
+    }
+
+    /// Collects the stars of `year` from the submission ledger and the saved leaderboard, if there is one.
+    pub fn read(year: Year) -> Result<Self, String> {
+        let mut stars = Stars::new(year);
+        stars.add_ledger(&Ledger::read_from_file(year)?);
+
+        match fs::read_to_string(leaderboard_path(year)) {
+            Ok(json) => {
+                let user_id = env::var(USER_ID_ENV).ok();
+                stars.add_leaderboard(&json, user_id.as_deref())?;
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.to_string()),
+        }
+
+        Ok(stars)
+    }
+
+    /// Adds a star for every part that was answered correctly.
+    pub fn add_ledger(&mut self, ledger: &Ledger) {
+        self.solved.extend(
+            ledger
+                .data
+                .iter()
+                .filter(|s| s.verdict == Verdict::Correct)
+                .map(|s| (s.day, s.part)),
+        );
+    }
+
+    /// Adds the stars of a member of a private leaderboard, in the JSON format of the leaderboard API.
+    /// Without a `user_id`, the leaderboard needs to have exactly one member.
+    pub fn add_leaderboard(&mut self, json: &str, user_id: Option<&str>) -> Result<(), String> {
+        let leaderboard = Leaderboard::try_from(json.to_string())?;
+        let member = leaderboard.member(user_id).map_err(|e| match user_id {
+            Some(_) => e,
+            None => format!(
+                "leaderboard has {} members, set `{USER_ID_ENV}` to select yours.",
+                leaderboard.members.len()
+            ),
+        })?;
+        self.solved.extend(member.stars.keys());
+        Ok(())
+    }
+
+    pub fn count(&self) -> usize {
+        self.solved
+            .iter()
+            .filter(|(day, _)| self.year.contains(*day))
+            .count()
+    }
+
+    fn has_star(&self, day: Day, part: u8) -> bool {
+        self.solved.contains(&(day, part))
+    }
+
+    /// Renders the stars as markdown, headed by `prefix` (e.g. `##`).
+    /// Lists every day of the event up to the last day with a star.
+    pub fn markdown(&self, prefix: &str) -> Vec<String> {
+        let mut lines = vec![
+            format!("{prefix} {} Results", self.year),
+            String::new(),
+            "| Day | Part 1 | Part 2 |".into(),
+            "| :---: | :---: | :---: |".into(),
+        ];
+
+        let last_day = self
+            .solved
+            .iter()
+            .map(|(day, _)| *day)
+            .filter(|day| self.year.contains(*day))
+            .max();
+
+        for day in last_day.map_or(vec![], |last| {
+            all_days(self.year).take_while(|day| *day <= last).collect()
+        }) {
+            let path = get_path_for_bin(PuzzleId::new(self.year, day));
+            let [part_1, part_2] =
+                [1, 2].map(|part| if self.has_star(day, part) { STAR } else { " " });
+            lines.push(format!(
+                "| [Day {}]({path}) | {part_1} | {part_2} |",
+                day.into_inner()
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push(format!(
+            "**Total: {} / {} {STAR}**",
+            self.count(),
+            usize::from(self.year.last_day()) * 2
+        ));
+
+        lines
+    }
+}
+
+/// Replaces the table of the year of `stars`. A table for a new year is added after the last existing table.
+fn update_content(s: &mut String, stars: &Stars) -> Result<(), Error> {
+    update_section(s, MARKER_PREFIX, stars.year, stars.markdown("##"))
+}
+
+/// Updates the star table of `year` in the readme. Years without stars are left out.
+pub fn update(year: Year) -> Result<(), Error> {
+    let stars = Stars::read(year).map_err(Error::Parser)?;
+    if stars.count() == 0 {
+        return Ok(());
+    }
+    update_readme(|readme| update_content(readme, &stars))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_content, Stars};
+    use crate::template::submissions::{Ledger, Submission, Verdict};
+    use crate::{day, year};
+
+    const LEADERBOARD: &str = r#"{
+        "event": "2024",
+        "owner_id": 3031,
+        "members": {
+            "3031": {
+                "id": 3031,
+                "name": "me",
+                "stars": 3,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1733029200 }, "2": { "get_star_ts": 1733029500 } },
+                    "3": { "1": { "get_star_ts": 1733202000 } }
+                }
+            },
+            "42": {
+                "id": 42,
+                "name": "teammate",
+                "stars": 1,
+                "completion_day_level": { "2": { "1": { "get_star_ts": 1733115600 } } }
+            }
+        }
+    }"#;
+
+    fn submission(part: u8, verdict: Verdict) -> Submission {
+        Submission {
+            day: day!(2),
+            part,
+            answer: "42".into(),
+            verdict,
+            timestamp: 0,
+            wait_secs: None,
+        }
+    }
+
+    #[test]
+    fn reads_leaderboard() {
+        let mut stars = Stars::new(year!(2024));
+        stars.add_leaderboard(LEADERBOARD, Some("3031")).unwrap();
+        assert_eq!(
+            stars.solved.into_iter().collect::<Vec<_>>(),
+            vec![(day!(1), 1), (day!(1), 2), (day!(3), 1)]
+        );
+
+        let mut stars = Stars::new(year!(2024));
+        assert_eq!(
+            stars.add_leaderboard(LEADERBOARD, None),
+            Err("leaderboard has 2 members, set `AOC_USER_ID` to select yours.".into())
+        );
+        assert!(stars.add_leaderboard(LEADERBOARD, Some("7")).is_err());
+    }
+
+    #[test]
+    fn reads_ledger() {
+        let mut stars = Stars::new(year!(2024));
+        stars.add_ledger(&Ledger {
+            data: vec![
+                submission(1, Verdict::TooHigh),
+                submission(1, Verdict::Correct),
+                submission(2, Verdict::Incorrect),
+            ],
+        });
+        assert_eq!(stars.count(), 1);
+        assert!(stars.has_star(day!(2), 1));
+    }
+
+    #[test]
+    fn renders_table() {
+        let mut stars = Stars::new(year!(2024));
+        stars.add_leaderboard(LEADERBOARD, Some("3031")).unwrap();
+
+        let bare_marker = "<!--- advent_readme_stars table --->";
+        let mut s = format!("foo\n{bare_marker}\nbar");
+        update_content(&mut s, &stars).unwrap();
+        update_content(&mut s, &stars).unwrap();
+
+        let expected = [
+            "foo",
+            bare_marker,
+            "",
+            "<!--- advent_readme_stars table 2024 --->",
+            "## 2024 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/2024-01.rs) | ⭐ | ⭐ |",
+            "| [Day 2](./src/bin/2024-02.rs) |   |   |",
+            "| [Day 3](./src/bin/2024-03.rs) | ⭐ |   |",
+            "",
+            "**Total: 3 / 50 ⭐**",
+            "<!--- advent_readme_stars table 2024 --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
@@ -0,0 +1,467 @@
+/// Renders stored benchmark timings as a markdown table, an SVG bar chart or a standalone HTML page.
+/// Days are marked when they exceed the budget passed to [`Report::with_budget`].
+use std::{fmt::Write, path::PathBuf};
+
+use crate::template::history::Record;
+use crate::template::timings::{format_nanos, Timing, Timings};
+use crate::template::{get_path_for_bin, PuzzleId, Year};
+
+const OVER_BUDGET_MARK: &str = "⚠️";
+
+const PARSE_COLOR: &str = "#9e9e9e";
+const PART_1_COLOR: &str = "#4c8eda";
+const PART_2_COLOR: &str = "#f0a35e";
+const OVER_BUDGET_COLOR: &str = "#d9534f";
+
+const CHART_LABEL_WIDTH: f64 = 64_f64;
+const CHART_BAR_WIDTH: f64 = 480_f64;
+const CHART_VALUE_WIDTH: f64 = 80_f64;
+const CHART_LEGEND_HEIGHT: f64 = 28_f64;
+const CHART_ROW_HEIGHT: f64 = 20_f64;
+const CHART_BAR_HEIGHT: f64 = 14_f64;
+
+/// Path of the benchmark chart of a year, e.g. `.assets/benchmarks-2024.svg`.
+#[must_use]
+pub fn chart_path(year: Year) -> PathBuf {
+    PathBuf::from(".assets").join(format!("benchmarks-{year}.svg"))
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Benchmark timings of one year, along with the environment they were measured in.
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub year: Year,
+    pub timings: Vec<Timing>,
+    /// Days slower than this (in nanoseconds) are marked.
+    pub budget_nanos: Option<f64>,
+    pub machine: Option<String>,
+    pub commit: Option<String>,
+}
+
+impl Report {
+    /// Creates a report of the days in the event of `year`, without a budget.
+    pub fn new(year: Year, timings: Timings) -> Self {
+        Report {
+            year,
+            // timings of days outside of the event, e.g. from a previous configuration, are left out.
+            timings: timings
+                .data
+                .into_iter()
+                .filter(|t| year.contains(t.day))
+                .collect(),
+            budget_nanos: None,
+            machine: None,
+            commit: None,
+        }
+    }
+
+    /// Marks days that take longer than `budget_nanos`.
+    #[must_use]
+    pub fn with_budget(mut self, budget_nanos: Option<f64>) -> Self {
+        self.budget_nanos = budget_nanos;
+        self
+    }
+
+    /// Adds the machine and commit of a benchmark run.
+    #[must_use]
+    pub fn with_environment(mut self, record: &Record) -> Self {
+        self.machine = Some(record.machine.clone());
+        self.commit.clone_from(&record.commit);
+        self
+    }
+
+    pub fn total_nanos(&self) -> f64 {
+        // folds from positive zero, `sum` of an empty iterator is `-0.0`.
+        self.timings
+            .iter()
+            .fold(0_f64, |total, t| total + t.total_nanos)
+    }
+
+    /// The parse column is only shown once a day has a separate parse stage.
+    fn has_parse(&self) -> bool {
+        self.timings.iter().any(|t| t.parse.is_some())
+    }
+
+    fn share(&self, timing: &Timing) -> String {
+        let total = self.total_nanos();
+        if total <= 0_f64 {
+            return "-".into();
+        }
+        format!("{:.1}%", timing.total_nanos / total * 100_f64)
+    }
+
+    fn is_over_budget(&self, timing: &Timing) -> bool {
+        self.budget_nanos
+            .is_some_and(|budget| timing.total_nanos > budget)
+    }
+
+    fn columns(&self, timing: &Timing) -> Vec<Option<f64>> {
+        let mut columns = vec![];
+        if self.has_parse() {
+            columns.push(timing.parse);
+        }
+        columns.extend([timing.part_1, timing.part_2]);
+        columns
+    }
+
+    fn headers(&self) -> Vec<&'static str> {
+        let mut headers = vec!["Day"];
+        if self.has_parse() {
+            headers.push("Parse");
+        }
+        headers.extend(["Part 1", "Part 2", "Share"]);
+        headers
+    }
+
+    fn environment(&self) -> Option<String> {
+        let machine = self.machine.as_deref()?;
+        Some(match &self.commit {
+            Some(commit) => format!("Measured on `{machine}` at commit `{commit}`."),
+            None => format!("Measured on `{machine}`."),
+        })
+    }
+
+    fn budget_note(&self) -> Option<String> {
+        let budget = self.budget_nanos?;
+        self.timings
+            .iter()
+            .any(|t| self.is_over_budget(t))
+            .then(|| {
+                format!(
+                    "{OVER_BUDGET_MARK} Slower than the budget of {} per day.",
+                    format_nanos(budget)
+                )
+            })
+    }
+
+    /// Renders the report as markdown, headed by `prefix` (e.g. `##`).
+    pub fn markdown(&self, prefix: &str) -> Vec<String> {
+        let headers = self.headers();
+        let mut lines = vec![
+            format!("{prefix} {} Benchmarks", self.year),
+            String::new(),
+            format!("| {} |", headers.join(" | ")),
+            format!("|{}", " :---: |".repeat(headers.len())),
+        ];
+
+        for timing in &self.timings {
+            let path = get_path_for_bin(PuzzleId::new(self.year, timing.day));
+            let mut cells = vec![format!("[Day {}]({path})", timing.day.into_inner())];
+            if self.is_over_budget(timing) {
+                cells[0].push_str(&format!(" {OVER_BUDGET_MARK}"));
+            }
+            cells.extend(
+                self.columns(timing)
+                    .into_iter()
+                    .map(|nanos| format!("`{}`", nanos.map_or_else(|| "-".into(), format_nanos))),
+            );
+            cells.push(self.share(timing));
+            lines.push(format!("| {} |", cells.join(" | ")));
+        }
+
+        lines.push(String::new());
+        lines.push(format!(
+            "**Total: {:.2}ms**",
+            self.total_nanos() / 1_000_000_f64
+        ));
+        lines.push(String::new());
+        lines.push(format!(
+            "![{} benchmarks](./{})",
+            self.year,
+            chart_path(self.year).display()
+        ));
+
+        let notes: Vec<String> = [self.budget_note(), self.environment()]
+            .into_iter()
+            .flatten()
+            .map(|note| format!("_{note}_"))
+            .collect();
+        if !notes.is_empty() {
+            lines.push(String::new());
+            lines.push(notes.join("<br>"));
+        }
+
+        lines
+    }
+
+    /// Renders a horizontal bar chart of the runtime of every day, split into parse, part 1 and part 2.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn svg(&self) -> String {
+        let max = self
+            .timings
+            .iter()
+            .map(|t| t.total_nanos)
+            .fold(0_f64, f64::max);
+        let scale = |nanos: f64| {
+            if max > 0_f64 {
+                nanos / max * CHART_BAR_WIDTH
+            } else {
+                0_f64
+            }
+        };
+
+        let width = CHART_LABEL_WIDTH + CHART_BAR_WIDTH + CHART_VALUE_WIDTH;
+        let height = CHART_LEGEND_HEIGHT + self.timings.len() as f64 * CHART_ROW_HEIGHT;
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="12">"#
+        );
+        svg.push_str("\n<style>text { fill: #888; }</style>\n");
+
+        let mut series = vec![];
+        if self.has_parse() {
+            series.push(("Parse", PARSE_COLOR));
+        }
+        series.extend([("Part 1", PART_1_COLOR), ("Part 2", PART_2_COLOR)]);
+
+        let mut x = CHART_LABEL_WIDTH;
+        for (name, color) in &series {
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{x}" y="6" width="12" height="12" fill="{color}"/><text x="{}" y="16">{name}</text>"#,
+                x + 16_f64
+            );
+            x += 80_f64;
+        }
+
+        for (i, timing) in self.timings.iter().enumerate() {
+            let y = CHART_LEGEND_HEIGHT + i as f64 * CHART_ROW_HEIGHT;
+            let text_y = y + CHART_BAR_HEIGHT - 3_f64;
+
+            let label_style = if self.is_over_budget(timing) {
+                format!(r#" style="fill: {OVER_BUDGET_COLOR}; font-weight: bold""#)
+            } else {
+                String::new()
+            };
+            let _ = writeln!(
+                svg,
+                r#"<text x="0" y="{text_y}"{label_style}>Day {}</text>"#,
+                timing.day.into_inner()
+            );
+
+            let segments = [
+                ("Parse", PARSE_COLOR, timing.parse),
+                ("Part 1", PART_1_COLOR, timing.part_1),
+                ("Part 2", PART_2_COLOR, timing.part_2),
+            ];
+
+            let mut x = CHART_LABEL_WIDTH;
+            for (name, color, nanos) in segments {
+                let Some(nanos) = nanos.filter(|n| *n > 0_f64) else {
+                    continue;
+                };
+                let bar_width = scale(nanos);
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{x:.1}" y="{y}" width="{bar_width:.1}" height="{CHART_BAR_HEIGHT}" fill="{color}"><title>{name}: {}</title></rect>"#,
+                    format_nanos(nanos)
+                );
+                x += bar_width;
+            }
+
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{text_y}">{}</text>"#,
+                x + 6_f64,
+                format_nanos(timing.total_nanos)
+            );
+        }
+
+        if let Some(budget) = self.budget_nanos.filter(|b| *b <= max) {
+            let x = CHART_LABEL_WIDTH + scale(budget);
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{x:.1}" y1="{CHART_LEGEND_HEIGHT}" x2="{x:.1}" y2="{height}" stroke="{OVER_BUDGET_COLOR}" stroke-dasharray="4 3"/>"#
+            );
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Renders a standalone HTML page with the table and chart, e.g. for a team dashboard.
+    pub fn html(&self) -> String {
+        let cell = |tag: &str, content: &str| format!("<{tag}>{content}</{tag}>");
+
+        let header: String = self.headers().iter().map(|h| cell("th", h)).collect();
+
+        let rows: String = self
+            .timings
+            .iter()
+            .map(|timing| {
+                let class = if self.is_over_budget(timing) {
+                    r#" class="over-budget""#
+                } else {
+                    ""
+                };
+                let mut cells = cell("td", &format!("Day {}", timing.day.into_inner()));
+                for nanos in self.columns(timing) {
+                    cells.push_str(&cell("td", &nanos.map_or_else(|| "-".into(), format_nanos)));
+                }
+                cells.push_str(&cell("td", &self.share(timing)));
+                format!("<tr{class}>{cells}</tr>\n")
+            })
+            .collect();
+
+        let notes: String = [self.budget_note(), self.environment()]
+            .into_iter()
+            .flatten()
+            .map(|note| {
+                // markdown code spans become `<code>` elements.
+                let note = escape_html(&note)
+                    .split('`')
+                    .enumerate()
+                    .map(|(i, s)| {
+                        if i % 2 == 1 {
+                            cell("code", s)
+                        } else {
+                            s.into()
+                        }
+                    })
+                    .collect::<String>();
+                format!("<p>{note}</p>\n")
+            })
+            .collect();
+
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code {year} Benchmarks</title>
+<style>
+body {{ font-family: sans-serif; max-width: 48rem; margin: 2rem auto; }}
+table {{ border-collapse: collapse; margin-bottom: 2rem; }}
+th, td {{ padding: 0.25rem 0.75rem; text-align: right; }}
+th:first-child, td:first-child {{ text-align: left; }}
+tr.over-budget {{ color: {OVER_BUDGET_COLOR}; font-weight: bold; }}
+</style>
+</head>
+<body>
+<h1>Advent of Code {year} Benchmarks</h1>
+<table>
+<thead><tr>{header}</tr></thead>
+<tbody>
+{rows}</tbody>
+<tfoot><tr><th>Total</th><th colspan="{colspan}">{total:.2}ms</th></tr></tfoot>
+</table>
+{svg}{notes}</body>
+</html>
+"#,
+            year = self.year,
+            colspan = self.headers().len() - 1,
+            total = self.total_nanos() / 1_000_000_f64,
+            svg = self.svg(),
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Report;
+    use crate::template::timings::{Timing, Timings};
+    use crate::{day, year};
+
+    fn timing(day: u8, parse: Option<f64>, part_1: f64, part_2: f64) -> Timing {
+        Timing {
+            parse,
+            part_1: Some(part_1),
+            part_2: Some(part_2),
+            total_nanos: parse.unwrap_or_default() + part_1 + part_2,
+            ..Timing::new(crate::template::Day::new(day).unwrap())
+        }
+    }
+
+    fn report() -> Report {
+        Report {
+            year: year!(2024),
+            timings: vec![
+                timing(1, None, 1e+6, 2e+6),
+                timing(2, Some(1e+6), 3e+6, 3e+6),
+            ],
+            budget_nanos: Some(5e+6),
+            machine: Some("ci<1>".into()),
+            commit: Some("abc1234".into()),
+        }
+    }
+
+    #[test]
+    fn renders_markdown() {
+        assert_eq!(
+            report().markdown("##"),
+            [
+                "## 2024 Benchmarks",
+                "",
+                "| Day | Parse | Part 1 | Part 2 | Share |",
+                "| :---: | :---: | :---: | :---: | :---: |",
+                "| [Day 1](./src/bin/2024-01.rs) | `-` | `1.0ms` | `2.0ms` | 30.0% |",
+                "| [Day 2](./src/bin/2024-02.rs) ⚠️ | `1.0ms` | `3.0ms` | `3.0ms` | 70.0% |",
+                "",
+                "**Total: 10.00ms**",
+                "",
+                "![2024 benchmarks](./.assets/benchmarks-2024.svg)",
+                "",
+                "_⚠️ Slower than the budget of 5.0ms per day._<br>_Measured on `ci<1>` at commit `abc1234`._",
+            ]
+            .map(String::from)
+        );
+    }
+
+    #[test]
+    fn omits_notes_without_budget_or_environment() {
+        let report = Report::new(
+            year!(2024),
+            Timings {
+                data: vec![timing(1, None, 1e+6, 2e+6)],
+            },
+        );
+        let markdown = report.markdown("##");
+        assert!(!markdown.iter().any(|l| l.contains("⚠️")));
+        assert!(!markdown.iter().any(|l| l.contains("Measured on")));
+        assert_eq!(markdown[2], "| Day | Part 1 | Part 2 | Share |");
+    }
+
+    #[test]
+    fn renders_svg_chart() {
+        let svg = report().svg();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        // one bar per measured part and a legend entry per series.
+        assert_eq!(svg.matches("<rect").count(), 5 + 3);
+        assert!(svg.contains("<title>Parse: 1.0ms</title>"));
+        assert!(svg.contains("stroke-dasharray"));
+        assert_eq!(svg.matches("font-weight: bold").count(), 1);
+    }
+
+    #[test]
+    fn renders_html_page() {
+        let html = report().html();
+        assert!(html.contains("<tr class=\"over-budget\"><td>Day 2</td>"));
+        assert!(html.contains("<td>Day 1</td><td>-</td><td>1.0ms</td><td>2.0ms</td><td>30.0%</td>"));
+        assert!(
+            html.contains("Measured on <code>ci&lt;1&gt;</code> at commit <code>abc1234</code>.")
+        );
+        assert!(html.contains("<svg "));
+        assert!(html.contains("10.00ms"));
+    }
+
+    #[test]
+    fn skips_days_outside_of_event() {
+        let report = Report::new(
+            year!(2025),
+            Timings {
+                data: vec![timing(2, None, 1.0, 1.0), timing(13, None, 1.0, 1.0)],
+            },
+        );
+        assert_eq!(report.timings.len(), 1);
+        assert_eq!(report.timings[0].day, day!(2));
+    }
+}
@@ -0,0 +1,326 @@
+/// Runs a single day against the inputs of every profile in `data/<year>/inputs/<profile>/` and tabulates the results.
+/// Useful to catch solutions that only work for one particular input.
+use std::{
+    fs,
+    process::{Command, Stdio},
+};
+
+use crate::template::{year_dir, PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+/// Name shown for the input at `data/<year>/inputs/NN.txt`.
+const DEFAULT_PROFILE: &str = "(default)";
+
+/// The outcome of one part for one profile.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartOutcome {
+    pub answer: Option<String>,
+    pub duration: Option<String>,
+    /// Why the part has no answer, e.g. `not implemented` or `panicked: <message>`.
+    pub problem: Option<String>,
+}
+
+/// The outcome of a whole run for one profile.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfileOutcome {
+    pub profile: String,
+    pub parts: [PartOutcome; 2],
+    pub panic: Option<String>,
+}
+
+impl ProfileOutcome {
+    fn problems(&self) -> Vec<String> {
+        let mut problems = vec![];
+        if let Some(panic) = &self.panic {
+            problems.push(format!("panicked: {panic}"));
+        }
+        for (i, part) in self.parts.iter().enumerate() {
+            if let Some(problem) = &part.problem {
+                problems.push(format!("part {} {problem}", i + 1));
+            } else if part.answer.is_none() && self.panic.is_none() {
+                problems.push(format!("part {} returned None", i + 1));
+            }
+        }
+        problems
+    }
+}
+
+/// Lists the profiles that have an input for `puzzle`, the default input first.
+pub fn find_profiles(puzzle: PuzzleId) -> Vec<Option<String>> {
+    let inputs_dir = year_dir(puzzle.year).join("inputs");
+    let file_name = format!("{}.txt", puzzle.day);
+
+    let mut profiles: Vec<String> = fs::read_dir(&inputs_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|e| e.path().join(&file_name).is_file())
+                .filter_map(|e| e.file_name().into_string().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    profiles.sort_unstable();
+
+    let mut res: Vec<Option<String>> = vec![];
+    if inputs_dir.join(&file_name).is_file() {
+        res.push(None);
+    }
+    res.extend(profiles.into_iter().map(Some));
+    res
+}
+
+pub fn run_inputs(puzzle: PuzzleId, is_release: bool) -> Vec<ProfileOutcome> {
+    let profiles = find_profiles(puzzle);
+
+    if profiles.is_empty() {
+        let (year, day) = (puzzle.year, puzzle.day);
+        eprintln!(
+            "No inputs found for day {puzzle}. Expected \"data/{year}/inputs/{day}.txt\" or \"data/{year}/inputs/<profile>/{day}.txt\"."
+        );
+        return vec![];
+    }
+
+    let outcomes: Vec<ProfileOutcome> = profiles
+        .into_iter()
+        .map(|profile| {
+            let name = profile.clone().unwrap_or_else(|| DEFAULT_PROFILE.into());
+            println!("Running day {puzzle} with input {ANSI_BOLD}{name}{ANSI_RESET}...");
+            run_profile(puzzle, profile.as_deref(), is_release, name)
+        })
+        .collect();
+
+    println!();
+    print_table(&outcomes);
+    outcomes
+}
+
+fn run_profile(
+    puzzle: PuzzleId,
+    profile: Option<&str>,
+    is_release: bool,
+    name: String,
+) -> ProfileOutcome {
+    let bin_name = puzzle.bin_name();
+    let mut args = vec!["run", "--quiet", "--bin", &bin_name];
+
+    if is_release {
+        args.push("--release");
+    }
+
+    args.push("--");
+
+    if let Some(profile) = profile {
+        args.extend(["--profile", profile]);
+    }
+
+    let output = Command::new("cargo")
+        .args(&args)
+        .stdin(Stdio::null())
+        .output();
+
+    match output {
+        Ok(output) => parse_output(
+            name,
+            &String::from_utf8_lossy(&output.stdout),
+            &String::from_utf8_lossy(&output.stderr),
+            output.status.success(),
+        ),
+        Err(e) => ProfileOutcome {
+            profile: name,
+            parts: Default::default(),
+            panic: Some(format!("could not run cargo: {e}")),
+        },
+    }
+}
+
+/// Extract answers and durations from the output of a solution binary.
+pub fn parse_output(profile: String, stdout: &str, stderr: &str, success: bool) -> ProfileOutcome {
+    let mut parts: [PartOutcome; 2] = Default::default();
+
+    for line in stdout.lines() {
+        // intermediate results are overwritten with a carriage return, only keep the final one.
+        let line = strip_ansi(line.rsplit('\r').next().unwrap_or(line));
+
+        let Some((part, rest)) = line.split_once(": ") else {
+            continue;
+        };
+
+        let index = match part {
+            "Part 1" => 0,
+            "Part 2" => 1,
+            _ => continue,
+        };
+
+        // failures carry a message instead of a duration.
+        if let Some(problem) = rest
+            .strip_prefix("✖ ")
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+        {
+            parts[index] = PartOutcome {
+                problem: Some(problem.replacen("error:", "failed:", 1)),
+                ..Default::default()
+            };
+            continue;
+        }
+
+        let (answer, duration) = match rest.rsplit_once(" (") {
+            Some((answer, duration)) => (
+                answer.trim(),
+                Some(duration.trim_end_matches(')').to_string()),
+            ),
+            None => (rest.trim(), None),
+        };
+
+        parts[index] = PartOutcome {
+            answer: match answer {
+                "✖" => None,
+                "▼" => Some("(multi-line)".into()),
+                answer => Some(answer.to_string()),
+            },
+            duration,
+            problem: None,
+        };
+    }
+
+    // panics of a single part are already reported as its problem.
+    let panic = (!success && parts.iter().all(|p| p.problem.is_none())).then(|| {
+        stderr
+            .lines()
+            .skip_while(|l| !l.contains("panicked at"))
+            .nth(1)
+            .or_else(|| stderr.lines().rev().find(|l| !l.trim().is_empty()))
+            .unwrap_or("non-zero exit status")
+            .trim()
+            .to_string()
+    });
+
+    ProfileOutcome {
+        profile,
+        parts,
+        panic,
+    }
+}
+
+fn strip_ansi(s: &str) -> String {
+    let mut res = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // skip until the end of the escape sequence.
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            res.push(c);
+        }
+    }
+    res
+}
+
+fn print_table(outcomes: &[ProfileOutcome]) {
+    let rows: Vec<[String; 6]> = outcomes
+        .iter()
+        .map(|o| {
+            let [p1, p2] = &o.parts;
+            let problems = o.problems();
+            [
+                o.profile.clone(),
+                p1.answer.clone().unwrap_or_else(|| "✖".into()),
+                p1.duration.clone().unwrap_or_else(|| "-".into()),
+                p2.answer.clone().unwrap_or_else(|| "✖".into()),
+                p2.duration.clone().unwrap_or_else(|| "-".into()),
+                if problems.is_empty() {
+                    "ok".into()
+                } else {
+                    problems.join(", ")
+                },
+            ]
+        })
+        .collect();
+
+    let header = ["Input", "Part 1", "Time", "Part 2", "Time", "Status"].map(String::from);
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|i| {
+            std::iter::once(&header)
+                .chain(&rows)
+                .map(|r| r[i].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let format_row = |row: &[String; 6]| {
+        row.iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join(" | ")
+            .trim_end()
+            .to_string()
+    };
+
+    println!("{ANSI_BOLD}{}{ANSI_RESET}", format_row(&header));
+    println!(
+        "{}",
+        widths
+            .iter()
+            .map(|w| "-".repeat(*w))
+            .collect::<Vec<_>>()
+            .join("-|-")
+    );
+    for row in &rows {
+        println!("{}", format_row(row));
+    }
+}
+
+/// Returns `true` if any profile panicked or did not produce an answer.
+pub fn has_problems(outcomes: &[ProfileOutcome]) -> bool {
+    outcomes.iter().any(|o| !o.problems().is_empty())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_output;
+
+    #[test]
+    fn parses_answers_and_durations() {
+        let stdout = "Part 1: \x1b[1m11\x1b[0m\rPart 1: \x1b[1m11\x1b[0m (7.3µs)\nPart 2: ✖\rPart 2: ✖             \n";
+        let res = parse_output("alice".into(), stdout, "", true);
+        assert_eq!(res.parts[0].answer.as_deref(), Some("11"));
+        assert_eq!(res.parts[0].duration.as_deref(), Some("7.3µs"));
+        assert_eq!(res.parts[1].answer, None);
+        assert_eq!(res.panic, None);
+        assert_eq!(res.problems(), vec!["part 2 returned None".to_string()]);
+    }
+
+    #[test]
+    fn parses_panics() {
+        let stderr = "thread 'main' panicked at src/bin/17.rs:12:5:\nindex out of bounds\nnote: run with `RUST_BACKTRACE=1`";
+        let res = parse_output("bob".into(), "", stderr, false);
+        assert_eq!(res.panic.as_deref(), Some("index out of bounds"));
+        assert_eq!(res.problems().len(), 1);
+    }
+
+    #[test]
+    fn parses_part_failures() {
+        let stdout = "Part 1: ✖ panicked: index out of bounds\nPart 2: ✖ error: no path (from 0,0)\n  caused by: grid is empty\n";
+        let res = parse_output("carol".into(), stdout, "", false);
+        assert_eq!(res.parts[0].answer, None);
+        assert_eq!(res.parts[1].duration, None);
+        assert_eq!(res.panic, None);
+        assert_eq!(
+            res.problems(),
+            vec![
+                "part 1 panicked: index out of bounds".to_string(),
+                "part 2 failed: no path (from 0,0)".to_string()
+            ]
+        );
+
+        let res = parse_output("dave".into(), "Part 1: ✖ not implemented\n", "", true);
+        assert_eq!(res.problems()[0], "part 1 not implemented");
+    }
+}
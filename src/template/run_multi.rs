@@ -1,38 +1,74 @@
-use std::{collections::HashSet, io};
+use std::{collections::HashSet, io, path::Path};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::counters;
+use crate::template::{get_path_for_bin, Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    count_allocs: bool,
+    count_instructions: bool,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
+    let mut has_valgrind = None;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let (days, unscaffolded): (Vec<Day>, Vec<Day>) = all_days(year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        .partition(|day| Path::new(&get_path_for_bin(PuzzleId::new(year, *day))).exists());
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    for day in days {
+        if need_space {
+            println!();
+        }
+        need_space = true;
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let puzzle = PuzzleId::new(year, day);
+        let output = child_commands::run_solution(
+            puzzle,
+            is_timed,
+            is_release,
+            count_allocs,
+            count_instructions,
+        )
+        .unwrap();
+
+        if output.is_empty() {
+            println!("Not solved.");
+        } else {
+            let mut val = child_commands::parse_exec_time(&output, day);
+            if count_instructions && val.is_missing_counters() {
+                if *has_valgrind.get_or_insert_with(counters::has_valgrind) {
+                    count_with_cachegrind(&mut val, puzzle, is_release);
+                } else {
+                    eprintln!("Warning: performance counters are not available and valgrind was not found, instructions are not counted.");
+                }
             }
-        });
+            timings.push(val);
+        }
+    }
+
+    if !unscaffolded.is_empty() {
+        if need_space {
+            println!();
+        }
+        println!(
+            "{ANSI_ITALIC}Skipped {} days that are not scaffolded yet.{ANSI_RESET}",
+            unscaffolded.len()
+        );
+    }
 
     if is_timed {
         let timings = Timings { data: timings };
@@ -46,6 +82,26 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Counts the solved parts of a day under cachegrind, for machines that do not expose performance counters.
+fn count_with_cachegrind(timing: &mut Timing, puzzle: PuzzleId, is_release: bool) {
+    let bin = child_commands::bin_path(puzzle, is_release);
+
+    for (part, nanos, counter_stats) in [
+        (1, timing.part_1, &mut timing.part_1_counters),
+        (2, timing.part_2, &mut timing.part_2_counters),
+    ] {
+        if nanos.is_none() || counter_stats.is_some() {
+            continue;
+        }
+
+        *counter_stats = counters::count_part(&bin, part);
+        match counter_stats {
+            Some(stats) => println!("Part {part}: {stats} {ANSI_ITALIC}(cachegrind){ANSI_RESET}"),
+            None => eprintln!("Could not count part {part} under cachegrind."),
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -59,37 +115,48 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::Error;
+    use crate::template::{
+        alloc::AllocStats, counters::CounterStats, get_path_for_bin, Day, PuzzleId,
+    };
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
+        path::{Path, PathBuf},
         process::{Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given puzzle
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        is_timed: bool,
+        is_release: bool,
+        count_allocs: bool,
+        count_instructions: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&get_path_for_bin(puzzle)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = puzzle.bin_name();
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
 
         if is_release {
             args.push("--release");
         }
 
+        if count_allocs {
+            args.extend(["--features", "alloc-stats"]);
+        }
+
+        if count_instructions {
+            args.extend(["--features", "perf-counters"]);
+        }
+
         if is_timed {
             // mirror `--time` flag to child invocations.
             args.push("--");
@@ -128,13 +195,15 @@ pub mod child_commands {
         Ok(output)
     }
 
+    /// Path of the solution bin built by [`run_solution`], e.g. `target/release/2024-05`.
+    pub fn bin_path(puzzle: PuzzleId, is_release: bool) -> PathBuf {
+        PathBuf::from("target")
+            .join(if is_release { "release" } else { "debug" })
+            .join(puzzle.bin_name())
+    }
+
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+        let mut timings = super::Timing::new(day);
 
         output
             .iter()
@@ -143,19 +212,27 @@ pub mod child_commands {
                     return None;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
+                let Some(nanos) = parse_time(l) else {
                     eprintln!("Could not parse timings from line: {l}");
                     return None;
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, nanos, AllocStats::parse(l), CounterStats::parse(l)))
             })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
+            .for_each(|(part, nanos, alloc_stats, counter_stats)| {
+                if part.contains("Parse") {
+                    timings.parse = Some(nanos);
+                    timings.parse_alloc = alloc_stats;
+                    timings.parse_counters = counter_stats;
+                } else if part.contains("Part 1") {
+                    timings.part_1 = Some(nanos);
+                    timings.part_1_alloc = alloc_stats;
+                    timings.part_1_counters = counter_stats;
                 } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                    timings.part_2 = Some(nanos);
+                    timings.part_2_alloc = alloc_stats;
+                    timings.part_2_counters = counter_stats;
                 }
 
                 timings.total_nanos += nanos;
@@ -168,25 +245,28 @@ pub mod child_commands {
         s.split(postfix).next()?.parse().ok()
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
+    fn parse_time(line: &str) -> Option<f64> {
         // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
+        parse_duration(str_timing)
+    }
+
+    /// Parse a formatted duration such as `31.6µs` into nanoseconds.
+    pub fn parse_duration(s: &str) -> Option<f64> {
+        match s {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
             s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
             s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
             s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        }
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -220,8 +300,8 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.part_1.unwrap(), 74.13_f64);
+            assert_approx_eq!(res.part_2.unwrap(), 74130000_f64);
         }
 
         #[test]
@@ -235,8 +315,56 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.part_1.unwrap(), 2000000000_f64);
+            assert_approx_eq!(res.part_2.unwrap(), 100000000_f64);
+        }
+
+        #[test]
+        fn parses_alloc_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [3 allocs, 96 bytes, peak 64 bytes]"
+                        .into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1_alloc.unwrap().allocations, 3);
+            assert_eq!(res.part_1_alloc.unwrap().peak_bytes, 64);
+            assert_eq!(res.part_2_alloc.is_none(), true);
+        }
+
+        #[test]
+        fn parses_counter_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [3 allocs, 96 bytes, peak 64 bytes] {1200 instructions, 34 cache misses}"
+                        .into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples) {5600 instructions}".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1_alloc.unwrap().allocations, 3);
+            assert_eq!(res.part_1_counters.unwrap().instructions, 1200);
+            assert_eq!(res.part_1_counters.unwrap().cache_misses, Some(34));
+            assert_eq!(res.part_2_counters.unwrap().cache_misses, None);
+            assert_approx_eq!(res.part_2.unwrap(), 74130000_f64);
+        }
+
+        #[test]
+        fn parses_parse_times() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: (2.00µs @ 5000 samples)".into(),
+                    "Part 1: 0 (1.00µs @ 10000 samples)".into(),
+                    "Part 2: 10 (3.00µs @ 3000 samples)".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.parse.unwrap(), 2000_f64);
+            assert_approx_eq!(res.part_1.unwrap(), 1000_f64);
+            assert_approx_eq!(res.total_nanos, 6000_f64);
         }
 
         #[test]
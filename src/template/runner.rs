@@ -1,55 +1,173 @@
 /// Encapsulates code that interacts with solution functions.
-use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, process, thread};
 
+#[cfg(all(feature = "alloc-stats", not(feature = "dhat-heap")))]
+use crate::template::alloc;
+use crate::template::alloc::AllocStats;
+use crate::template::answer::panic_message;
+use crate::template::aoc_client::SubmissionOutcome;
+#[cfg(all(feature = "perf-counters", target_os = "linux"))]
+use crate::template::counters::Counters;
+use crate::template::counters::{self, CounterStats};
+use crate::template::profiler;
+use crate::template::readme_stars;
+use crate::template::submissions::{self, Ledger, Submission};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_client, Outcome, PartResult, PuzzleId, ANSI_ITALIC, ANSI_RESET};
+
+/// Runs one part of a solution and prints its outcome. Panics are caught, so the other part still runs.
+pub fn run_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) -> Outcome {
+    if let Some(flame_part) = profiler::flame_part() {
+        // only the profiled part runs, the other one is skipped.
+        return if flame_part == part {
+            profiler::profile(func, input, puzzle, part)
+        } else {
+            Outcome::Unsolved
+        };
+    }
+
+    if counters::only_part().is_some_and(|only_part| only_part != part) {
+        return Outcome::Unsolved;
+    }
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (outcome, duration, samples, exec_stats) =
+        run_timed(func, input, |outcome| print_result(outcome, &part_str, ""));
+
+    let mut stats_str = format_duration(&duration, samples);
+    exec_stats.append_to(&mut stats_str);
+
+    print_result(&outcome, &part_str, &stats_str);
+    submit_result(&outcome, puzzle, part);
+
+    outcome
+}
+
+/// Exits with a non-zero status if a part returned an error or panicked.
+pub fn exit_on_failure(outcomes: &[Outcome]) {
+    if outcomes.iter().any(Outcome::is_failure) {
+        process::exit(1);
+    }
+}
+
+/// Parses the input once before the parts run. The parse is timed and benched like a part.
+/// Exits with an error message if parsing panics.
+pub fn run_parse<I: Clone, P>(func: impl Fn(I) -> P, input: I) -> P {
+    print!("Parse:");
+    let _ = stdout().flush();
+
+    let (result, base_time, exec_stats) = run_once(&func, input.clone());
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let parsed = match result {
+        Ok(parsed) => parsed,
+        Err(payload) => {
+            println!("\rParse: ✖ panicked: {}", panic_message(payload.as_ref()));
+            process::exit(1);
+        }
+    };
+
+    let (duration, samples) = if is_timed() {
+        bench(func, input, &base_time)
+    } else {
+        (base_time, 1)
+    };
+
+    let mut stats_str = format_duration(&duration, samples);
+    exec_stats.append_to(&mut stats_str);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    println!("\rParse:{stats_str}");
+    parsed
+}
+
+fn is_timed() -> bool {
+    env::args().any(|x| x == "--time")
+}
+
+/// Heap usage and counter readings of a single execution, present when enabled by the `alloc-stats` and `perf-counters` features.
+#[derive(Clone, Copy, Debug, Default)]
+struct ExecStats {
+    alloc: Option<AllocStats>,
+    counters: Option<CounterStats>,
+}
+
+impl ExecStats {
+    /// Appends the stats to the formatted duration of a part, e.g. ` [3 allocs, ...] {1234 instructions, ...}`.
+    fn append_to(&self, stats_str: &mut String) {
+        if let Some(alloc_stats) = self.alloc {
+            stats_str.push_str(&format!(" {alloc_stats}"));
+        }
+        if let Some(counter_stats) = self.counters {
+            stats_str.push_str(&format!(" {counter_stats}"));
+        }
     }
 }
 
+/// Executes `func` once, catching panics.
+/// With the `alloc-stats` and `perf-counters` features, heap usage and counter readings of the execution are returned as well.
+fn run_once<I, T>(func: &impl Fn(I) -> T, input: I) -> (thread::Result<T>, Duration, ExecStats) {
+    let timer = Instant::now();
+
+    #[cfg(feature = "dhat-heap")]
+    let _profiler = dhat::Profiler::new_heap();
+
+    #[cfg(all(feature = "alloc-stats", not(feature = "dhat-heap")))]
+    alloc::reset();
+
+    #[cfg(all(feature = "perf-counters", target_os = "linux"))]
+    let counters = Counters::start();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| func(input)));
+
+    #[cfg(all(feature = "perf-counters", target_os = "linux"))]
+    let counters = counters.and_then(Counters::stop);
+    #[cfg(not(all(feature = "perf-counters", target_os = "linux")))]
+    let counters = None;
+
+    #[cfg(all(feature = "alloc-stats", not(feature = "dhat-heap")))]
+    let alloc = Some(alloc::snapshot());
+    #[cfg(not(all(feature = "alloc-stats", not(feature = "dhat-heap"))))]
+    let alloc = None;
+
+    (result, timer.elapsed(), ExecStats { alloc, counters })
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+///
+/// Only parts that produce an answer are benched.
+/// With the `alloc-stats` and `perf-counters` features, heap usage and counter readings of the first execution are returned as well.
+fn run_timed<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = {
-        let input = input.clone();
-
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
+    hook: impl Fn(&Outcome),
+) -> (Outcome, Duration, u128, ExecStats) {
+    let (result, base_time, exec_stats) = run_once(&func, input.clone());
 
-        func(input)
+    let outcome = match result {
+        Ok(result) => result.into_outcome(),
+        Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
     };
-    let base_time = timer.elapsed();
 
-    hook(&result);
+    hook(&outcome);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let run = if outcome.is_solved() && is_timed() {
         bench(func, input, &base_time)
     } else {
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (outcome, run.0, run.1, exec_stats)
 }
 
 fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
@@ -94,53 +212,45 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+pub(crate) fn print_result(outcome: &Outcome, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
-        }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+    let str = match outcome {
+        Outcome::Answer(answer) => format!("{part}: {ANSI_BOLD}{answer}{ANSI_RESET}{duration_str}"),
+        Outcome::MultiLine(_) => format!("{part}: ▼ {duration_str}"),
+        Outcome::Unsolved => format!("{part}: ✖             "),
+        Outcome::Unimplemented => format!("{part}: ✖ not implemented"),
+        Outcome::Failed(chain) => format!("{part}: ✖ error: {}", chain[0]),
+        Outcome::Panicked(message) => format!("{part}: ✖ panicked: {message}"),
+    };
+
+    if is_intermediate_result {
+        print!("{}", str.trim_end());
+        return;
+    }
+
+    print!("\r");
+    println!("{str}");
+
+    match outcome {
+        Outcome::MultiLine(answer) => println!("{answer}"),
+        Outcome::Failed(chain) => {
+            for cause in &chain[1..] {
+                println!("  caused by: {cause}");
             }
         }
+        _ => {}
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. a session cookie is configured.
+fn submit_result(outcome: &Outcome, puzzle: PuzzleId, part: u8) {
     let args: Vec<String> = env::args().collect();
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+    if !args.contains(&"--submit".into()) || !outcome.is_solved() {
+        return;
     }
 
     if args.len() < 3 {
@@ -156,14 +266,49 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let Outcome::Answer(answer) = outcome else {
+        eprintln!(
+            "Not submitting part {part}: multi-line answers need to be read and submitted by hand."
+        );
+        return;
+    };
+    let mut ledger = match Ledger::read_from_file(puzzle.year) {
+        Ok(ledger) => ledger,
+        Err(e) => {
+            eprintln!("Not submitting `{answer}`: could not read the submission ledger, fix or remove it first: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(refusal) = ledger.check(puzzle.day, part, answer, submissions::now()) {
+        eprintln!("Not submitting `{answer}`: {refusal}");
+        return;
+    }
+
+    for warning in ledger.range_warnings(puzzle.day, part, answer) {
+        eprintln!("Warning: {warning}");
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+    match aoc_client::submit(puzzle, part, answer) {
+        Ok(outcome) => {
+            println!("{outcome}");
+            ledger.push(Submission::new(puzzle.day, part, answer, &outcome));
+            if let Err(e) = ledger.store_file(puzzle.year) {
+                eprintln!("failed to store submission: {e}");
+            }
+            if matches!(outcome, SubmissionOutcome::Correct)
+                && readme_stars::update(puzzle.year).is_err()
+            {
+                eprintln!("Failed to update the star table.");
+            }
+        }
+        Err(e) => {
+            eprintln!("failed to submit result: {e}");
+            process::exit(1);
+        }
+    }
 }
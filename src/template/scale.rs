@@ -0,0 +1,485 @@
+/// Measures how the run time of a solution grows with the size of its input, used by `cargo time <day> --scale`.
+/// Inputs come from the generator of the day (see [`generate`](crate::template::generate)), with sizes doubling up to a maximum.
+/// Every size is benched by the solution binary itself, the curves are fitted against common complexity classes.
+use std::fmt::{Display, Write as _};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{fs, iter};
+
+use crate::template::fuzz::{crashes, Solution};
+use crate::template::generate::{Case, REFERENCE_ARG};
+use crate::template::run_inputs::ProfileOutcome;
+use crate::template::run_multi::child_commands::parse_duration;
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// All sizes are generated from the same seed, so the inputs only differ in size.
+const SEED: u64 = 1;
+
+/// Curves with fewer points are not fitted.
+const MIN_FIT_POINTS: usize = 3;
+
+/// A faster growing complexity class needs to reduce the error by this fraction to be picked.
+const TIE: f64 = 0.1;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Options {
+    pub max_size: usize,
+    pub timeout: Duration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Complexity {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+    Exponential,
+}
+
+impl Complexity {
+    const ALL: [Complexity; 7] = [
+        Complexity::Constant,
+        Complexity::Logarithmic,
+        Complexity::Linear,
+        Complexity::Linearithmic,
+        Complexity::Quadratic,
+        Complexity::Cubic,
+        Complexity::Exponential,
+    ];
+
+    /// Growth function at size `n`, with `n >= 1`. `log n` is taken of `n + 1`, so it is not zero at `n = 1`.
+    #[allow(clippy::cast_possible_truncation)]
+    fn growth(self, n: f64) -> f64 {
+        match self {
+            Complexity::Constant => 1.0,
+            Complexity::Logarithmic => (n + 1.0).log2(),
+            Complexity::Linear => n,
+            Complexity::Linearithmic => n * (n + 1.0).log2(),
+            Complexity::Quadratic => n * n,
+            Complexity::Cubic => n * n * n,
+            Complexity::Exponential => 2_f64.powi(n.min(1024.0) as i32),
+        }
+    }
+
+    /// Fits `time = a + b * growth(n)` with `a, b >= 0`, weighted by `1 / time²` so that every size counts alike.
+    /// Returns the sum of the squared relative errors.
+    fn error(self, points: &[(f64, f64)]) -> f64 {
+        let samples: Vec<(f64, f64, f64)> = points
+            .iter()
+            .map(|&(n, t)| (self.growth(n), t, 1.0 / (t * t)))
+            .collect();
+        if samples.iter().any(|(f, _, _)| !f.is_finite()) {
+            return f64::INFINITY;
+        }
+
+        let sum = |term: fn(f64, f64) -> f64| {
+            samples.iter().map(|&(f, t, w)| w * term(f, t)).sum::<f64>()
+        };
+        let (s, s_f, s_t, s_ff, s_ft) = (
+            sum(|_, _| 1.0),
+            sum(|f, _| f),
+            sum(|_, t| t),
+            sum(|f, _| f * f),
+            sum(|f, t| f * t),
+        );
+
+        let determinant = s * s_ff - s_f * s_f;
+        let (a, b) = if determinant.abs() <= f64::EPSILON * s * s_ff {
+            // the growth is the same at every size, only the overhead is fitted.
+            (s_t / s, 0.0)
+        } else {
+            let b = (s * s_ft - s_f * s_t) / determinant;
+            let a = (s_t - b * s_f) / s;
+            match (a >= 0.0, b >= 0.0) {
+                (true, true) => (a, b),
+                (false, _) => (0.0, (s_ft / s_ff).max(0.0)),
+                (true, false) => (s_t / s, 0.0),
+            }
+        };
+
+        samples
+            .iter()
+            .map(|&(f, t, _)| ((t - a - b * f) / t).powi(2))
+            .sum()
+    }
+}
+
+impl Display for Complexity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Complexity::Constant => "O(1)",
+            Complexity::Logarithmic => "O(log n)",
+            Complexity::Linear => "O(n)",
+            Complexity::Linearithmic => "O(n log n)",
+            Complexity::Quadratic => "O(n²)",
+            Complexity::Cubic => "O(n³)",
+            Complexity::Exponential => "O(2ⁿ)",
+        })
+    }
+}
+
+/// The complexity class that fits a curve best, and the exponent `k` of the power law `time ~ n^k` through it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fit {
+    pub complexity: Complexity,
+    pub exponent: f64,
+}
+
+/// Run times of one part over growing input sizes, in nanoseconds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Curve {
+    pub name: String,
+    pub points: Vec<(usize, f64)>,
+}
+
+impl Curve {
+    pub fn new(name: &str) -> Self {
+        Curve {
+            name: name.into(),
+            points: vec![],
+        }
+    }
+
+    fn nanos_at(&self, size: usize) -> Option<f64> {
+        self.points
+            .iter()
+            .find(|(n, _)| *n == size)
+            .map(|(_, t)| *t)
+    }
+
+    /// Picks the complexity class that fits best. Ties go to the slower growing class.
+    /// The exponent is fitted to the larger half of the sizes, as small inputs are dominated by constant overhead.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fit(&self) -> Option<Fit> {
+        let points: Vec<(f64, f64)> = self
+            .points
+            .iter()
+            .filter(|(_, t)| *t > 0.0)
+            .map(|&(n, t)| (n as f64, t))
+            .collect();
+
+        if points.len() < MIN_FIT_POINTS {
+            return None;
+        }
+
+        let complexity = Complexity::ALL
+            .into_iter()
+            .map(|c| (c, c.error(&points)))
+            .reduce(|best, next| {
+                if next.1 < best.1 * (1.0 - TIE) {
+                    next
+                } else {
+                    best
+                }
+            })?
+            .0;
+
+        let larger = &points[points.len() - MIN_FIT_POINTS.max(points.len().div_ceil(2))..];
+        let count = larger.len() as f64;
+        let mean_x = larger.iter().map(|(n, _)| n.ln()).sum::<f64>() / count;
+        let mean_y = larger.iter().map(|(_, t)| t.ln()).sum::<f64>() / count;
+        let (covariance, variance) = larger.iter().fold((0.0, 0.0), |(c, v), (n, t)| {
+            let dx = n.ln() - mean_x;
+            (c + dx * (t.ln() - mean_y), v + dx * dx)
+        });
+
+        Some(Fit {
+            complexity,
+            exponent: covariance / variance,
+        })
+    }
+}
+
+/// Size from which on the order of two curves stays the same, interpolated between the measured sizes in log space.
+/// Earlier swaps are ignored, small sizes take nanoseconds and are noisy. The flag is `true` if `a` becomes the slower one.
+#[allow(clippy::cast_precision_loss)]
+pub fn crossover(a: &Curve, b: &Curve) -> Option<(f64, bool)> {
+    let differences: Vec<(f64, f64)> = a
+        .points
+        .iter()
+        .filter_map(|&(n, t)| Some(((n as f64).ln(), t.ln() - b.nanos_at(n)?.ln())))
+        .collect();
+
+    differences
+        .windows(2)
+        .rfind(|w| (w[0].1 > 0.0) != (w[1].1 > 0.0))
+        .map(|w| {
+            let ((x0, d0), (x1, d1)) = (w[0], w[1]);
+            ((x0 + (x1 - x0) * d0 / (d0 - d1)).exp(), d1 > 0.0)
+        })
+}
+
+/// Sizes doubling from 1 to `max_size`, which is always included.
+pub fn sizes(max_size: usize) -> Vec<usize> {
+    let max_size = max_size.max(1);
+    let mut sizes: Vec<usize> = iter::successors(Some(1_usize), |n| n.checked_mul(2))
+        .take_while(|&n| n < max_size)
+        .collect();
+    sizes.push(max_size);
+    sizes
+}
+
+/// Renders the curves as CSV, one row per size. Sizes a curve has no time for are left empty.
+pub fn csv(inputs: &[(usize, usize)], curves: &[Curve]) -> String {
+    let mut csv = String::from("size,bytes");
+    for curve in curves {
+        let _ = write!(csv, ",{}_ns", curve.name.to_lowercase().replace(' ', "_"));
+    }
+    csv.push('\n');
+
+    for &(size, bytes) in inputs {
+        let _ = write!(csv, "{size},{bytes}");
+        for curve in curves {
+            csv.push(',');
+            if let Some(nanos) = curve.nanos_at(size) {
+                let _ = write!(csv, "{nanos:.0}");
+            }
+        }
+        csv.push('\n');
+    }
+    csv
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Bench durations of both parts in nanoseconds, `None` for parts without an answer.
+fn durations(outcome: &ProfileOutcome) -> [Option<f64>; 2] {
+    outcome.parts.each_ref().map(|part| {
+        part.answer.as_ref()?;
+        parse_duration(part.duration.as_ref()?.split('@').next()?.trim())
+    })
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: Option<f64>) -> String {
+    nanos.map_or_else(
+        || "-".into(),
+        |n| format!("{:.1?}", Duration::from_nanos(n as u64)),
+    )
+}
+
+fn csv_path(puzzle: PuzzleId) -> PathBuf {
+    PathBuf::from("target")
+        .join("scale")
+        .join(format!("{}.csv", puzzle.bin_name()))
+}
+
+fn write_csv(path: &Path, csv: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, csv)
+}
+
+/// Benches a solution, and its reference implementation if it has one, on generated inputs of growing size.
+/// A run that fails or times out stops the curves of that binary, the others continue.
+pub fn scale(puzzle: PuzzleId, options: Options) {
+    let solution = Solution::build(puzzle, options.timeout);
+
+    let runs: Vec<(&str, Vec<&str>)> = if solution.has_reference {
+        vec![
+            ("Solution", vec!["--time"]),
+            ("Reference", vec!["--time", REFERENCE_ARG]),
+        ]
+    } else {
+        vec![("Solution", vec!["--time"])]
+    };
+    let mut curves: Vec<Curve> = ["Part 1", "Part 2", "Reference 1", "Reference 2"]
+        .iter()
+        .take(runs.len() * 2)
+        .map(|name| Curve::new(name))
+        .collect();
+    let mut running = vec![true; runs.len()];
+    let mut inputs = vec![];
+
+    println!(
+        "Scaling day {puzzle} up to size {} (timeout {:.1?} per run).\n",
+        options.max_size, options.timeout
+    );
+    let header: Vec<String> = curves.iter().map(|c| format!("{:>12}", c.name)).collect();
+    println!("{:>8} | {:>10} | {}", "Size", "Bytes", header.join(" | "));
+
+    for size in sizes(options.max_size) {
+        if !running.contains(&true) {
+            break;
+        }
+
+        let input = match solution.generate(Case { seed: SEED, size }) {
+            Ok(input) => input,
+            Err(failure) => {
+                println!("Stopped at size {size}: {failure}.");
+                break;
+            }
+        };
+        inputs.push((size, input.len()));
+
+        for (i, (name, args)) in runs.iter().enumerate() {
+            if !running[i] {
+                continue;
+            }
+
+            print!("\r{ANSI_ITALIC}size {size}: running {name}...{ANSI_RESET}");
+            let _ = std::io::stdout().flush();
+
+            let outcome = solution
+                .run(&input, args)
+                .and_then(|outcome| crashes(&outcome).map(|()| outcome));
+            match outcome {
+                Ok(outcome) => {
+                    for (part, nanos) in durations(&outcome).into_iter().enumerate() {
+                        if let Some(nanos) = nanos {
+                            curves[i * 2 + part].points.push((size, nanos));
+                        }
+                    }
+                }
+                Err(failure) => {
+                    println!("\r{ANSI_BOLD}{name} stopped at size {size}:{ANSI_RESET} {failure}.");
+                    running[i] = false;
+                }
+            }
+        }
+
+        let times: Vec<String> = curves
+            .iter()
+            .map(|c| format!("{:>12}", format_nanos(c.nanos_at(size))))
+            .collect();
+        println!("\r{size:>8} | {:>10} | {}", input.len(), times.join(" | "));
+    }
+
+    println!();
+    for curve in &curves {
+        match curve.fit() {
+            Some(fit) => println!(
+                "{ANSI_BOLD}{}:{ANSI_RESET} {} {ANSI_ITALIC}(time grows with n^{:.2}){ANSI_RESET}",
+                curve.name, fit.complexity, fit.exponent
+            ),
+            None => println!(
+                "{ANSI_BOLD}{}:{ANSI_RESET} too few sizes to estimate.",
+                curve.name
+            ),
+        }
+    }
+
+    let pairs: &[(usize, usize)] = if solution.has_reference {
+        &[(0, 1), (2, 0), (3, 1)]
+    } else {
+        &[(0, 1)]
+    };
+    for &(a, b) in pairs {
+        if let Some((size, a_is_slower)) = crossover(&curves[a], &curves[b]) {
+            let (slower, faster) = if a_is_slower { (a, b) } else { (b, a) };
+            println!(
+                "{} is slower than {} from size ≈ {size:.0} on.",
+                curves[slower].name,
+                curves[faster].name.to_lowercase()
+            );
+        }
+    }
+
+    let path = csv_path(puzzle);
+    match write_csv(&path, &csv(&inputs, &curves)) {
+        Ok(()) => println!("\nWrote the curves to \"{}\".", path.display()),
+        Err(e) => eprintln!("Failed to write the curves: {e}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{crossover, csv, sizes, Complexity, Curve};
+
+    #[allow(clippy::cast_precision_loss)]
+    fn curve(name: &str, time: impl Fn(f64) -> f64) -> Curve {
+        Curve {
+            name: name.into(),
+            points: sizes(4096)
+                .into_iter()
+                .map(|n| (n, time(n as f64)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn doubles_sizes() {
+        assert_eq!(sizes(16), vec![1, 2, 4, 8, 16]);
+        assert_eq!(sizes(20), vec![1, 2, 4, 8, 16, 20]);
+        assert_eq!(sizes(0), vec![1]);
+    }
+
+    #[test]
+    fn fits_complexity_classes() {
+        let fit = |time: fn(f64) -> f64| curve("Part 1", time).fit().unwrap();
+
+        // the overhead is part of the fitted model, but still flattens the empirical exponent.
+        let linear = fit(|n| 50_000.0 + 30.0 * n);
+        assert_eq!(linear.complexity, Complexity::Linear);
+        assert!(linear.exponent < 1.0);
+
+        let quadratic = fit(|n| 4.0 * n * n);
+        assert_eq!(quadratic.complexity, Complexity::Quadratic);
+        assert!((quadratic.exponent - 2.0).abs() < 1e-6);
+
+        // exponential solutions time out early, so only small sizes are measured.
+        let exponential = Curve {
+            name: "Part 1".into(),
+            points: sizes(16)
+                .into_iter()
+                .map(|n| (n, 500.0 + 3.0 * 2_f64.powi(n as i32)))
+                .collect(),
+        };
+        assert_eq!(
+            exponential.fit().unwrap().complexity,
+            Complexity::Exponential
+        );
+        assert_eq!(
+            fit(|n| 10.0 * n * n.log2()).complexity,
+            Complexity::Linearithmic
+        );
+        assert_eq!(fit(|_| 1200.0).complexity, Complexity::Constant);
+
+        let short = Curve {
+            name: "Part 2".into(),
+            points: vec![(1, 10.0), (2, 20.0)],
+        };
+        assert_eq!(short.fit(), None);
+    }
+
+    #[test]
+    fn finds_crossover() {
+        let optimized = curve("Part 1", |n| 2000.0 + 10.0 * n);
+        let naive = curve("Reference 1", |n| n * n);
+
+        let (size, naive_is_slower) = crossover(&naive, &optimized).unwrap();
+        assert!(naive_is_slower);
+        assert!((40.0..70.0).contains(&size), "crossover at {size}");
+
+        // only the last swap counts, the first sizes are noisy.
+        let mut noisy = optimized.clone();
+        noisy.points[0].1 = 1e9;
+        assert_eq!(
+            crossover(&noisy, &naive).map(|(_, slower)| slower),
+            Some(false)
+        );
+        assert_eq!(crossover(&optimized, &optimized), None);
+    }
+
+    #[test]
+    fn renders_csv() {
+        let part_1 = Curve {
+            name: "Part 1".into(),
+            points: vec![(1, 120.0), (2, 250.4)],
+        };
+        let reference = Curve {
+            name: "Reference 1".into(),
+            points: vec![(1, 900.0)],
+        };
+        assert_eq!(
+            csv(&[(1, 4), (2, 9)], &[part_1, reference]),
+            "size,bytes,part_1_ns,reference_1_ns\n1,4,120,900\n2,9,250,\n"
+        );
+    }
+}
@@ -0,0 +1,460 @@
+/// Module that keeps a ledger of submitted answers and their verdicts.
+/// Used to refuse submissions that are known to be wrong before they reach the server.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{AnswerHint, SubmissionOutcome};
+use crate::template::{year_dir, Day, Year};
+
+const SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+fn file_path(year: Year) -> PathBuf {
+    year_dir(year).join(SUBMISSIONS_FILE_NAME)
+}
+
+/// The server verdict of a submission, as stored in the ledger.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    RateLimited,
+    WrongLevel,
+    Unrecognized,
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub timestamp: u64,
+    /// Wait time imposed by the server after this submission, in seconds.
+    pub wait_secs: Option<u64>,
+}
+
+/// Every submission made from this repository.
+#[derive(Clone, Debug, Default)]
+pub struct Ledger {
+    pub data: Vec<Submission>,
+}
+
+/// Reasons to not send an answer to the server.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    KnownWrong(Verdict),
+    Cooldown(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with answer `{answer}`.")
+            }
+            Refusal::KnownWrong(verdict) => {
+                write!(f, "this answer was already submitted and was {verdict}.")
+            }
+            Refusal::Cooldown(wait) => write!(
+                f,
+                "the server asked to wait before submitting again, {}s left.",
+                wait.as_secs()
+            ),
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::RateLimited => "rate limited",
+            Verdict::WrongLevel => "for the wrong level",
+            Verdict::Unrecognized => "not recognized",
+        };
+        f.write_str(s)
+    }
+}
+
+impl Verdict {
+    fn as_key(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::RateLimited => "rate_limited",
+            Verdict::WrongLevel => "wrong_level",
+            Verdict::Unrecognized => "unrecognized",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        Some(match key {
+            "correct" => Verdict::Correct,
+            "too_high" => Verdict::TooHigh,
+            "too_low" => Verdict::TooLow,
+            "incorrect" => Verdict::Incorrect,
+            "rate_limited" => Verdict::RateLimited,
+            "wrong_level" => Verdict::WrongLevel,
+            "unrecognized" => Verdict::Unrecognized,
+            _ => return None,
+        })
+    }
+
+    fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+}
+
+impl Submission {
+    pub fn new(day: Day, part: u8, answer: &str, outcome: &SubmissionOutcome) -> Self {
+        let (verdict, wait) = match outcome {
+            SubmissionOutcome::Correct => (Verdict::Correct, None),
+            SubmissionOutcome::Incorrect { hint, cooldown } => (
+                match hint {
+                    Some(AnswerHint::TooHigh) => Verdict::TooHigh,
+                    Some(AnswerHint::TooLow) => Verdict::TooLow,
+                    None => Verdict::Incorrect,
+                },
+                *cooldown,
+            ),
+            SubmissionOutcome::RateLimited(wait) => (Verdict::RateLimited, *wait),
+            SubmissionOutcome::WrongLevel => (Verdict::WrongLevel, None),
+            SubmissionOutcome::Unrecognized(_) => (Verdict::Unrecognized, None),
+        };
+
+        Submission {
+            day,
+            part,
+            answer: answer.into(),
+            verdict,
+            timestamp: now(),
+            wait_secs: wait.map(|d| d.as_secs()),
+        }
+    }
+}
+
+impl Ledger {
+    /// Dehydrate the ledger to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let path = file_path(year);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the ledger from a JSON file. If not present, returns an empty ledger.
+    /// Fails if the file can not be read or parsed, since an empty ledger would disable every check.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = file_path(year);
+        match fs::read_to_string(&path) {
+            Ok(json) => Ledger::try_from(json).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Ledger::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
+    }
+
+    pub fn push(&mut self, submission: Submission) {
+        self.data.push(submission);
+    }
+
+    fn for_part(&self, day: Day, part: u8) -> impl Iterator<Item = &Submission> {
+        self.data
+            .iter()
+            .filter(move |s| s.day == day && s.part == part)
+    }
+
+    /// Returns the correct answer of a part, if it was solved.
+    pub fn correct_answer(&self, day: Day, part: u8) -> Option<&str> {
+        self.for_part(day, part)
+            .find(|s| s.verdict == Verdict::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    /// Unix timestamp until which the server does not accept answers.
+    pub fn cooldown_until(&self) -> Option<u64> {
+        self.data
+            .iter()
+            .filter_map(|s| Some(s.timestamp + s.wait_secs?))
+            .max()
+    }
+
+    /// Checks whether `answer` should be submitted at unix time `now`.
+    pub fn check(&self, day: Day, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        if let Some(correct) = self.correct_answer(day, part) {
+            return Err(Refusal::AlreadySolved(correct.into()));
+        }
+
+        if let Some(previous) = self
+            .for_part(day, part)
+            .find(|s| s.answer == answer && s.verdict.is_wrong())
+        {
+            return Err(Refusal::KnownWrong(previous.verdict));
+        }
+
+        match self.cooldown_until() {
+            Some(until) if until > now => Err(Refusal::Cooldown(Duration::from_secs(until - now))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns warnings for numeric answers that contradict earlier "too high" / "too low" hints.
+    pub fn range_warnings(&self, day: Day, part: u8, answer: &str) -> Vec<String> {
+        let Ok(value) = answer.trim().parse::<i128>() else {
+            return vec![];
+        };
+
+        self.for_part(day, part)
+            .filter_map(|s| {
+                let previous = s.answer.trim().parse::<i128>().ok()?;
+                match s.verdict {
+                    Verdict::TooHigh if value >= previous => Some(format!(
+                        "`{value}` is not lower than `{previous}`, which was too high."
+                    )),
+                    Verdict::TooLow if value <= previous => Some(format!(
+                        "`{value}` is not higher than `{previous}`, which was too low."
+                    )),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Current unix time in seconds.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Ledger> for JsonValue {
+    fn from(value: Ledger) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Ledger {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Ledger {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_key().into()),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "wait_secs".into(),
+            match value.wait_secs {
+                Some(x) => JsonValue::Number(x as f64),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected submission.part to be a number.")? as u8;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>().cloned())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| Verdict::from_key(v))
+            .ok_or("Expected submission.verdict to be a verdict.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected submission.timestamp to be a number.")? as u64;
+
+        let wait_secs = json
+            .get("wait_secs")
+            .and_then(|v| v.get::<f64>().copied())
+            .map(|v| v as u64);
+
+        Ok(Submission {
+            day,
+            part,
+            answer,
+            verdict,
+            timestamp,
+            wait_secs,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Ledger, Refusal, Submission, Verdict};
+    use crate::day;
+
+    fn submission(answer: &str, verdict: Verdict, timestamp: u64, wait: Option<u64>) -> Submission {
+        Submission {
+            day: day!(1),
+            part: 1,
+            answer: answer.into(),
+            verdict,
+            timestamp,
+            wait_secs: wait,
+        }
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let ledger = Ledger {
+            data: vec![submission("42", Verdict::TooHigh, 0, Some(60))],
+        };
+        assert_eq!(
+            ledger.check(day!(1), 1, "42", 1000),
+            Err(Refusal::KnownWrong(Verdict::TooHigh))
+        );
+        assert_eq!(ledger.check(day!(1), 1, "41", 1000), Ok(()));
+        assert_eq!(ledger.check(day!(1), 2, "42", 1000), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let ledger = Ledger {
+            data: vec![submission("7", Verdict::Correct, 0, None)],
+        };
+        assert_eq!(
+            ledger.check(day!(1), 1, "8", 1000),
+            Err(Refusal::AlreadySolved("7".into()))
+        );
+    }
+
+    #[test]
+    fn enforces_cooldown() {
+        let ledger = Ledger {
+            data: vec![submission("1", Verdict::Incorrect, 100, Some(60))],
+        };
+        assert_eq!(
+            ledger.check(day!(1), 1, "2", 130),
+            Err(Refusal::Cooldown(std::time::Duration::from_secs(30)))
+        );
+        assert_eq!(ledger.check(day!(1), 1, "2", 160), Ok(()));
+    }
+
+    #[test]
+    fn warns_about_answers_out_of_range() {
+        let ledger = Ledger {
+            data: vec![
+                submission("100", Verdict::TooHigh, 0, None),
+                submission("10", Verdict::TooLow, 0, None),
+            ],
+        };
+        assert_eq!(ledger.range_warnings(day!(1), 1, "50").len(), 0);
+        assert_eq!(ledger.range_warnings(day!(1), 1, "150").len(), 1);
+        assert_eq!(ledger.range_warnings(day!(1), 1, "5").len(), 1);
+        assert_eq!(ledger.range_warnings(day!(1), 1, "abc").len(), 0);
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let ledger = Ledger {
+            data: vec![submission("42", Verdict::TooLow, 1_700_000_000, Some(60))],
+        };
+        let json = tinyjson::JsonValue::from(ledger).stringify().unwrap();
+        let ledger = Ledger::try_from(json).unwrap();
+        assert_eq!(ledger.data.len(), 1);
+        assert_eq!(ledger.data[0].verdict, Verdict::TooLow);
+        assert_eq!(ledger.data[0].wait_secs, Some(60));
+        assert_eq!(ledger.data[0].timestamp, 1_700_000_000);
+    }
+
+    #[test]
+    fn rejects_truncated_json() {
+        let json = r#"{ "data": [{ "day": "01", "part": 1, "answer": "42""#;
+        assert!(Ledger::try_from(json.to_string()).is_err());
+    }
+}
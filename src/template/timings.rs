@@ -1,17 +1,74 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::alloc::AllocStats;
+use crate::template::counters::CounterStats;
+use crate::template::run_multi::child_commands::parse_duration;
+use crate::template::{year_dir, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+const TIMINGS_FILE_NAME: &str = "timings.json";
 
-/// Represents benchmark times for a single day.
+fn file_path(year: Year) -> PathBuf {
+    year_dir(year).join(TIMINGS_FILE_NAME)
+}
+
+/// Represents benchmark times for a single day, in nanoseconds.
+/// Heap usage is only present when the day was run with the `alloc-stats` feature,
+/// instruction counts only when it was run with `--metric instructions`.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Only present for days with a separate parse stage.
+    pub parse: Option<f64>,
+    pub part_1: Option<f64>,
+    pub part_2: Option<f64>,
     pub total_nanos: f64,
+    pub parse_alloc: Option<AllocStats>,
+    pub part_1_alloc: Option<AllocStats>,
+    pub part_2_alloc: Option<AllocStats>,
+    pub parse_counters: Option<CounterStats>,
+    pub part_1_counters: Option<CounterStats>,
+    pub part_2_counters: Option<CounterStats>,
+}
+
+impl Timing {
+    /// Creates an empty timing for `day`, without any measurements.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            parse_alloc: None,
+            part_1_alloc: None,
+            part_2_alloc: None,
+            parse_counters: None,
+            part_1_counters: None,
+            part_2_counters: None,
+        }
+    }
+
+    /// Returns `true` if a part was solved, but its instructions were not counted.
+    pub fn is_missing_counters(&self) -> bool {
+        (self.part_1.is_some() && self.part_1_counters.is_none())
+            || (self.part_2.is_some() && self.part_2_counters.is_none())
+    }
+}
+
+/// Format nanoseconds the same way the runner prints durations, e.g. `31.6µs`.
+#[must_use]
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.max(0_f64).round() as u64);
+    format!("{duration:.1?}")
 }
 
 /// Represents benchmark times for a set of days.
@@ -23,18 +80,25 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let path = file_path(year);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Fails if the file can not be read or parsed, so stored timings are never overwritten by accident.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = file_path(year);
+        match fs::read_to_string(&path) {
+            Ok(json) => Timings::try_from(json).map_err(|e| format!("{}: {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(format!("{}: {e}", path.display())),
+        }
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -51,7 +115,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -114,8 +178,12 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        if let Some(parse) = value.parse {
+            map.insert("parse".into(), JsonValue::Number(parse));
+        }
+
+        let part_1 = value.part_1.map(JsonValue::Number);
+        let part_2 = value.part_2.map(JsonValue::Number);
 
         map.insert(
             "part_1".into(),
@@ -133,6 +201,30 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if let Some(stats) = value.parse_alloc {
+            map.insert("parse_alloc".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = value.part_1_alloc {
+            map.insert("part_1_alloc".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = value.part_2_alloc {
+            map.insert("part_2_alloc".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = value.parse_counters {
+            map.insert("parse_counters".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = value.part_1_counters {
+            map.insert("part_1_counters".into(), JsonValue::from(stats));
+        }
+
+        if let Some(stats) = value.part_2_counters {
+            map.insert("part_2_counters".into(), JsonValue::from(stats));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -151,26 +243,172 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        let parse = json
+            .get("parse")
+            .map(|v| v.get::<f64>().copied())
+            .map(|v| v.ok_or("Expected timing.parse to be a number."))
+            .transpose()?;
+
         let part_1 = json
             .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+            .and_then(parse_part_nanos)
+            .ok_or("Expected timing.part_1 to be null or number.")?;
 
         let part_2 = json
             .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+            .and_then(parse_part_nanos)
+            .ok_or("Expected timing.part_2 to be null or number.")?;
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let parse_alloc = json
+            .get("parse_alloc")
+            .map(AllocStats::try_from)
+            .transpose()?;
+
+        let part_1_alloc = json
+            .get("part_1_alloc")
+            .map(AllocStats::try_from)
+            .transpose()?;
+
+        let part_2_alloc = json
+            .get("part_2_alloc")
+            .map(AllocStats::try_from)
+            .transpose()?;
+
+        let parse_counters = json
+            .get("parse_counters")
+            .map(CounterStats::try_from)
+            .transpose()?;
+
+        let part_1_counters = json
+            .get("part_1_counters")
+            .map(CounterStats::try_from)
+            .transpose()?;
+
+        let part_2_counters = json
+            .get("part_2_counters")
+            .map(CounterStats::try_from)
+            .transpose()?;
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse,
+            part_1,
+            part_2,
             total_nanos,
+            parse_alloc,
+            part_1_alloc,
+            part_2_alloc,
+            parse_counters,
+            part_1_counters,
+            part_2_counters,
+        })
+    }
+}
+
+/// Reads a part timing as nanoseconds.
+/// Older `timings.json` files stored pre-formatted strings such as `"31.6µs"`, these are still accepted.
+fn parse_part_nanos(value: &JsonValue) -> Option<Option<f64>> {
+    match value {
+        JsonValue::Null => Some(None),
+        JsonValue::Number(nanos) => Some(Some(*nanos)),
+        JsonValue::String(s) => parse_duration(s).map(Some),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<AllocStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected alloc stats to be a JSON object.")?;
+
+        let get = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as usize)
+                .ok_or(format!("Expected alloc stats.{key} to be a number."))
+        };
+
+        Ok(AllocStats {
+            allocations: get("allocations")?,
+            bytes: get("bytes")?,
+            peak_bytes: get("peak_bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<CounterStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: CounterStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "instructions".into(),
+            JsonValue::Number(value.instructions as f64),
+        );
+
+        if let Some(cache_misses) = value.cache_misses {
+            map.insert(
+                "cache_misses".into(),
+                JsonValue::Number(cache_misses as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for CounterStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected counter stats to be a JSON object.")?;
+
+        let get = |key: &str| {
+            json.get(key)
+                .map(|v| v.get::<f64>().map(|v| *v as u64))
+                .map(|v| v.ok_or(format!("Expected counter stats.{key} to be a number.")))
+                .transpose()
+        };
+
+        Ok(CounterStats {
+            instructions: get("instructions")?
+                .ok_or("Expected counter stats to have key `instructions`.")?,
+            cache_misses: get("cache_misses")?,
         })
     }
 }
@@ -187,22 +425,21 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(1e+7),
+                    part_2: Some(2e+7),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(3e+7),
+                    part_2: Some(4e+7),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
+                    part_1: Some(4e+7),
                     total_nanos: 4e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
@@ -213,16 +450,59 @@ mod tests {
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "day": "01", "part_1": 1000000, "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1, Some(1_000_000_f64));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_legacy_string_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.5ms", "part_2": "31.6µs", "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1, Some(1_500_000_f64));
+            assert_eq!(timing.part_2, Some(31_600_f64));
+        }
+
+        #[test]
+        fn handles_alloc_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": 1000, "part_2": null, "total_nanos": 1000, "part_1_alloc": { "allocations": 3, "bytes": 96, "peak_bytes": 64 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_alloc.unwrap();
+            assert_eq!(stats.allocations, 3);
+            assert_eq!(stats.bytes, 96);
+            assert_eq!(stats.peak_bytes, 64);
+            assert_eq!(timing.part_2_alloc, None);
+        }
+
+        #[test]
+        fn handles_parse_timings() {
+            let json = r#"{ "data": [{ "day": "01", "parse": 500, "part_1": 1000, "part_2": null, "total_nanos": 1500 }] }"#.to_string();
+            let timing = Timings::try_from(json).unwrap().data[0].clone();
+            assert_eq!(timing.parse, Some(500_f64));
+
+            let json = r#"{ "data": [{ "day": "01", "part_1": 1000, "part_2": null, "total_nanos": 1000 }] }"#.to_string();
+            assert_eq!(Timings::try_from(json).unwrap().data[0].parse, None);
+        }
+
+        #[test]
+        fn handles_counter_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": 1000, "part_2": 2000, "total_nanos": 3000, "part_1_counters": { "instructions": 1200, "cache_misses": 34 }, "part_2_counters": { "instructions": 5600 } }] }"#.to_string();
+            let timing = Timings::try_from(json).unwrap().data[0].clone();
+            let stats = timing.part_1_counters.unwrap();
+            assert_eq!(stats.instructions, 1200);
+            assert_eq!(stats.cache_misses, Some(34));
+            assert_eq!(timing.part_2_counters.unwrap().cache_misses, None);
+            assert_eq!(timing.parse_counters, None);
+            assert_eq!(timing.is_missing_counters(), false);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -278,48 +558,44 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: Some(1e+6),
+                    part_2: Some(2e+6),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
+                    part_1: Some(1e+6),
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![Timing::new(day!(1))],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
+            template::alloc::AllocStats,
+            template::counters::CounterStats,
             template::timings::{Timing, Timings},
         };
 
@@ -329,12 +605,7 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,12 +620,7 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
             };
             let merged = timings.merge(&other);
 
@@ -365,6 +631,44 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_stats_of_days_not_rerun() {
+            let alloc = AllocStats {
+                allocations: 3,
+                bytes: 96,
+                peak_bytes: 64,
+            };
+            let counters = CounterStats {
+                instructions: 1200,
+                cache_misses: Some(34),
+            };
+            let timings = Timings {
+                data: vec![Timing {
+                    parse: Some(500_f64),
+                    part_1: Some(1000_f64),
+                    parse_alloc: Some(alloc),
+                    part_2_alloc: Some(alloc),
+                    parse_counters: Some(counters),
+                    part_1_counters: Some(counters),
+                    ..Timing::new(day!(1))
+                }],
+            };
+            let other = Timings {
+                data: vec![Timing::new(day!(2))],
+            };
+
+            let merged = timings.merge(&other);
+            let kept = &merged.data[0];
+            assert_eq!(kept.day, day!(1));
+            assert_eq!(kept.parse, Some(500_f64));
+            assert_eq!(kept.parse_alloc, Some(alloc));
+            assert_eq!(kept.part_1_alloc, None);
+            assert_eq!(kept.part_2_alloc, Some(alloc));
+            assert_eq!(kept.parse_counters, Some(counters));
+            assert_eq!(kept.part_1_counters, Some(counters));
+            assert_eq!(kept.part_2_counters, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -0,0 +1,377 @@
+/// Watches the files of a day and re-runs its example tests and real input on every change.
+/// Changes are detected by polling modification times, no platform specific APIs or external tools are needed.
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::run_inputs::{parse_output, PartOutcome};
+use crate::template::{get_path_for_bin, year_dir, PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Maximum number of compiler error lines shown when a build fails.
+const MAX_ERROR_LINES: usize = 12;
+
+/// Modification time of every watched file.
+pub type Snapshot = HashMap<PathBuf, SystemTime>;
+
+/// The result of running the example tests of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TestSummary {
+    Passed(usize),
+    Failed { passed: usize, failed: Vec<String> },
+    BuildFailed(Vec<String>),
+}
+
+/// Lists the files that affect the result of a day.
+/// Examples are listed on every call, so example files created while watching are picked up.
+pub fn watched_paths(puzzle: PuzzleId) -> Vec<PathBuf> {
+    let data_dir = year_dir(puzzle.year);
+    let day = puzzle.day.to_string();
+
+    let mut paths = vec![
+        PathBuf::from(get_path_for_bin(puzzle)),
+        data_dir.join("inputs").join(format!("{day}.txt")),
+    ];
+
+    // the library is shared by every day, only the binary of the watched day is listed.
+    collect_rust_files(Path::new("src"), Path::new("src/bin"), &mut paths);
+    collect_rust_files(
+        Path::new("mygrid/src"),
+        Path::new("mygrid/src/bin"),
+        &mut paths,
+    );
+
+    if let Ok(entries) = fs::read_dir(data_dir.join("examples")) {
+        paths.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(&day) && name.ends_with(".txt"))
+                }),
+        );
+    }
+
+    paths
+}
+
+fn collect_rust_files(dir: &Path, skip: &Path, paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(Result::ok).map(|e| e.path()) {
+        if path == skip {
+            continue;
+        } else if path.is_dir() {
+            collect_rust_files(&path, skip, paths);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            paths.push(path);
+        }
+    }
+}
+
+/// Records the modification time of every existing file in `paths`.
+pub fn take_snapshot(paths: &[PathBuf]) -> Snapshot {
+    paths
+        .iter()
+        .filter_map(|p| Some((p.clone(), fs::metadata(p).ok()?.modified().ok()?)))
+        .collect()
+}
+
+/// Returns every file that was added, removed or modified between two snapshots.
+pub fn changed_paths(old: &Snapshot, new: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = new
+        .iter()
+        .filter(|(path, modified)| old.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .chain(old.keys().filter(|p| !new.contains_key(*p)).cloned())
+        .collect();
+
+    changed.sort_unstable();
+    changed
+}
+
+/// Extract the outcome of `cargo test` from its output.
+/// Works with `--quiet`, which only lists failing tests by name in the `failures:` block at the end.
+pub fn parse_test_output(stdout: &str, stderr: &str) -> TestSummary {
+    let Some(result) = stdout.lines().find(|l| l.starts_with("test result:")) else {
+        return TestSummary::BuildFailed(
+            stderr
+                .lines()
+                .skip_while(|l| !l.starts_with("error"))
+                .take(MAX_ERROR_LINES)
+                .map(String::from)
+                .collect(),
+        );
+    };
+
+    let words: Vec<&str> = result.split_whitespace().collect();
+    let count = |label: &str| {
+        words
+            .windows(2)
+            .find(|w| w[1].starts_with(label))
+            .and_then(|w| w[0].parse().ok())
+            .unwrap_or(0)
+    };
+    let passed = count("passed");
+
+    if count("failed") == 0 {
+        return TestSummary::Passed(passed);
+    }
+
+    let mut failed: Vec<String> = stdout
+        .lines()
+        .rev()
+        .take_while(|l| *l != "failures:")
+        .filter_map(|l| l.strip_prefix("    "))
+        .map(String::from)
+        .collect();
+    failed.reverse();
+
+    TestSummary::Failed { passed, failed }
+}
+
+/// Describes how the answer of a part changed compared to the previous run, `None` on the first run.
+pub fn describe_change(previous: Option<&PartOutcome>, current: &PartOutcome) -> Option<String> {
+    let previous = previous?;
+    if previous.answer == current.answer {
+        Some("unchanged".into())
+    } else {
+        Some(format!("was {}", previous.answer.as_deref().unwrap_or("✖")))
+    }
+}
+
+fn cargo(args: &[&str]) -> Option<(String, String, bool)> {
+    let output = Command::new("cargo")
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+
+    Some((
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+        output.status.success(),
+    ))
+}
+
+fn print_test_summary(summary: &TestSummary) {
+    match summary {
+        TestSummary::Passed(passed) => println!("Examples: {passed} passed"),
+        TestSummary::Failed { passed, failed } => println!(
+            "Examples: {passed} passed, {} failed ({})",
+            failed.len(),
+            failed.join(", ")
+        ),
+        TestSummary::BuildFailed(errors) => {
+            println!("Build failed:");
+            for line in errors {
+                println!("  {line}");
+            }
+        }
+    }
+}
+
+/// Runs the example tests and the real input once. Returns the answers if the solution ran.
+fn run_once(
+    puzzle: PuzzleId,
+    is_release: bool,
+    input_args: &[String],
+    previous: Option<&[PartOutcome; 2]>,
+) -> Option<[PartOutcome; 2]> {
+    let bin_name = puzzle.bin_name();
+
+    let Some((stdout, stderr, _)) = cargo(&["test", "--quiet", "--bin", &bin_name]) else {
+        eprintln!("Could not run cargo.");
+        return None;
+    };
+
+    let summary = parse_test_output(&stdout, &stderr);
+    print_test_summary(&summary);
+
+    if matches!(summary, TestSummary::BuildFailed(_)) {
+        return None;
+    }
+
+    let mut args = vec!["run", "--quiet", "--bin", &bin_name];
+    if is_release {
+        args.push("--release");
+    }
+    args.push("--");
+    args.extend(input_args.iter().map(String::as_str));
+
+    let (stdout, stderr, success) = cargo(&args)?;
+    let outcome = parse_output(String::new(), &stdout, &stderr, success);
+
+    for (i, part) in outcome.parts.iter().enumerate() {
+        let answer = match (&part.answer, &part.problem) {
+            (Some(answer), _) => answer.clone(),
+            (None, Some(problem)) => format!("✖ {problem}"),
+            (None, None) => "✖".into(),
+        };
+        let duration = part
+            .duration
+            .as_ref()
+            .map_or_else(String::new, |d| format!(" ({d})"));
+        let change = describe_change(previous.map(|p| &p[i]), part)
+            .map_or_else(String::new, |c| format!(" {ANSI_ITALIC}({c}){ANSI_RESET}"));
+
+        println!(
+            "Part {}: {ANSI_BOLD}{answer}{ANSI_RESET}{duration}{change}",
+            i + 1
+        );
+    }
+
+    if let Some(panic) = outcome.panic {
+        println!("Failed: {panic}");
+    }
+
+    Some(outcome.parts)
+}
+
+/// Watches `puzzle` until the process is interrupted.
+/// `input_args` are passed to the solution binary, e.g. `--input` or `--profile`.
+pub fn watch(puzzle: PuzzleId, is_release: bool, input_args: &[String]) {
+    println!("Watching day {puzzle}. Press Ctrl-C to stop.");
+    println!();
+
+    let mut snapshot = take_snapshot(&watched_paths(puzzle));
+    let mut previous = run_once(puzzle, is_release, input_args, None);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let current = take_snapshot(&watched_paths(puzzle));
+        let changed = changed_paths(&snapshot, &current);
+        if changed.is_empty() {
+            continue;
+        }
+        snapshot = current;
+
+        println!();
+        println!(
+            "{ANSI_BOLD}Changed:{ANSI_RESET} {}",
+            changed
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        if let Some(parts) = run_once(puzzle, is_release, input_args, previous.as_ref()) {
+            previous = Some(parts);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    use super::{
+        changed_paths, describe_change, parse_test_output, watched_paths, Snapshot, TestSummary,
+    };
+    use crate::template::run_inputs::PartOutcome;
+    use crate::{day, template::PuzzleId, year};
+
+    #[test]
+    fn detects_changed_paths() {
+        let t = SystemTime::UNIX_EPOCH;
+        let old: Snapshot = [("a".into(), t), ("b".into(), t), ("c".into(), t)].into();
+        let new: Snapshot = [
+            ("a".into(), t),
+            ("b".into(), t + Duration::from_secs(1)),
+            ("d".into(), t),
+        ]
+        .into();
+
+        assert_eq!(
+            changed_paths(&old, &new),
+            ["b", "c", "d"].map(PathBuf::from).to_vec()
+        );
+        assert!(changed_paths(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn watches_library_but_not_other_days() {
+        let paths = watched_paths(PuzzleId::new(year!(2024), day!(1)));
+        assert!(paths.contains(&PathBuf::from("./src/bin/2024-01.rs")));
+        assert!(paths.contains(&PathBuf::from("src/lib.rs")));
+        assert!(paths.contains(&PathBuf::from("src/collections.rs")));
+        assert!(paths.contains(&PathBuf::from("src/template/watch.rs")));
+        assert!(paths.contains(&PathBuf::from("mygrid/src/grid.rs")));
+        assert!(!paths.iter().any(|p| p.starts_with("src/bin")));
+    }
+
+    #[test]
+    fn parses_test_output() {
+        // Output of `cargo test --quiet`.
+        let stdout = "
+running 2 tests
+. 1/2
+tests::test_part_two --- FAILED
+
+failures:
+
+---- tests::test_part_two stdout ----
+
+thread 'tests::test_part_two' (30830) panicked at src/bin/2024-01.rs:5:28:
+assertion `left == right` failed
+  left: 1
+ right: 2
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    tests::test_part_two
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+        assert_eq!(
+            parse_test_output(stdout, ""),
+            TestSummary::Failed {
+                passed: 1,
+                failed: vec!["tests::test_part_two".into()]
+            }
+        );
+
+        let stdout = "test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s";
+        assert_eq!(parse_test_output(stdout, ""), TestSummary::Passed(2));
+
+        let stderr = "warning: unused\nerror[E0308]: mismatched types\n --> src/bin/2024-01.rs:3:5";
+        assert_eq!(
+            parse_test_output("", stderr),
+            TestSummary::BuildFailed(vec![
+                "error[E0308]: mismatched types".into(),
+                " --> src/bin/2024-01.rs:3:5".into()
+            ])
+        );
+    }
+
+    #[test]
+    fn describes_answer_changes() {
+        let part = |answer: &str| PartOutcome {
+            answer: Some(answer.into()),
+            ..Default::default()
+        };
+        assert_eq!(describe_change(None, &part("1")), None);
+        assert_eq!(
+            describe_change(Some(&part("1")), &part("1")).as_deref(),
+            Some("unchanged")
+        );
+        assert_eq!(
+            describe_change(Some(&part("1")), &part("2")).as_deref(),
+            Some("was 1")
+        );
+    }
+}
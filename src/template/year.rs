@@ -0,0 +1,258 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::Day;
+
+/// The first year Advent of Code took place.
+const FIRST_YEAR: u16 = 2015;
+
+/// The first year with the shortened, 12-day event.
+const FIRST_SHORT_YEAR: u16 = 2025;
+
+/// A valid year of Advent of Code (i.e. 2015 or later).
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a valid year of advent,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Returns the default year, read from the `AOC_YEAR` environment variable.
+    pub fn current() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// Returns the last day of the event of this year.
+    /// Events up to 2024 run for 25 days, later ones for 12 days.
+    /// Can be overridden with the `AOC_DAYS_<year>` environment variable, e.g. `AOC_DAYS_2025=12`.
+    pub fn last_day(self) -> u8 {
+        env::var(format!("AOC_DAYS_{}", self.0))
+            .ok()
+            .and_then(|days| days.trim().parse().ok())
+            .filter(|days| (1..=25).contains(days))
+            .unwrap_or(if self.0 >= FIRST_SHORT_YEAR { 12 } else { 25 })
+    }
+
+    /// Returns `true` if `day` is part of the event of this year.
+    pub fn contains(self, day: Day) -> bool {
+        day <= self.last_day()
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.trim().parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year of {FIRST_YEAR} or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies a single puzzle by its year and day.
+///
+/// # Display
+/// This value displays as `<year>/<day>`, which is also accepted by the CLI.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2024).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2024/08");
+/// assert_eq!(puzzle.bin_name(), "2024-08");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Parses `<year>/<day>`, `<year>-<day>` or a plain `<day>`.
+    /// A plain day belongs to `default_year`.
+    pub fn parse(s: &str, default_year: Option<Year>) -> Result<Self, PuzzleIdFromStrError> {
+        let (year, day) = match s.split_once(['/', '-']) {
+            Some((year, day)) => (
+                year.parse()
+                    .map_err(|_| PuzzleIdFromStrError::InvalidYear)?,
+                day,
+            ),
+            None => (default_year.ok_or(PuzzleIdFromStrError::MissingYear)?, s),
+        };
+
+        let day: Day = day
+            .parse()
+            .map_err(|_| PuzzleIdFromStrError::InvalidDay(year.last_day()))?;
+
+        if !year.contains(day) {
+            return Err(PuzzleIdFromStrError::InvalidDay(year.last_day()));
+        }
+
+        Ok(Self::new(year, day))
+    }
+
+    /// Name of the solution binary, e.g. `2024-08` for `src/bin/2024-08.rs`.
+    pub fn bin_name(&self) -> String {
+        format!("{}-{}", self.year, self.day)
+    }
+
+    /// Returns the puzzle of the current day if the event of the current year is running, `None` otherwise.
+    #[cfg(feature = "today")]
+    pub fn today() -> Option<Self> {
+        use chrono::{Datelike, FixedOffset, Utc};
+
+        let offset = FixedOffset::east_opt(super::day::SERVER_UTC_OFFSET * 3600)?;
+        let year = Utc::now().with_timezone(&offset).year();
+        Some(Self::new(
+            Year::new(u16::try_from(year).ok()?)?,
+            Day::today()?,
+        ))
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdFromStrError;
+
+    /// Plain days belong to the year set in `AOC_YEAR`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Year::current())
+    }
+}
+
+/// An error which can be returned when parsing a [`PuzzleId`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum PuzzleIdFromStrError {
+    InvalidYear,
+    /// Contains the last day of the event.
+    InvalidDay(u8),
+    MissingYear,
+}
+
+impl Error for PuzzleIdFromStrError {}
+
+impl Display for PuzzleIdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PuzzleIdFromStrError::InvalidYear => YearFromStrError.fmt(f),
+            PuzzleIdFromStrError::InvalidDay(last_day) => {
+                write!(f, "expecting a day number between 1 and {last_day}")
+            }
+            PuzzleIdFromStrError::MissingYear => {
+                f.write_str("no year given. Use `<year>/<day>`, pass `--year` or set `AOC_YEAR`")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!("invalid year `", $year, "`, expecting 2015 or later"),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PuzzleId, PuzzleIdFromStrError, Year};
+    use crate::day;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().ok(), Some(year!(2023)));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("abc".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn parses_puzzle_ids() {
+        let expected = PuzzleId::new(year!(2023), day!(5));
+        assert_eq!(PuzzleId::parse("2023/05", None), Ok(expected));
+        assert_eq!(PuzzleId::parse("2023-5", None), Ok(expected));
+        assert_eq!(PuzzleId::parse("5", Some(year!(2023))), Ok(expected));
+        assert_eq!(
+            PuzzleId::parse("5", None),
+            Err(PuzzleIdFromStrError::MissingYear)
+        );
+        assert_eq!(
+            PuzzleId::parse("2023/26", None),
+            Err(PuzzleIdFromStrError::InvalidDay(25))
+        );
+        assert_eq!(
+            PuzzleId::parse("2025/13", None),
+            Err(PuzzleIdFromStrError::InvalidDay(12))
+        );
+        assert_eq!(
+            PuzzleId::parse("1999/1", None),
+            Err(PuzzleIdFromStrError::InvalidYear)
+        );
+    }
+
+    #[test]
+    fn formats_puzzle_ids() {
+        let puzzle = PuzzleId::new(year!(2023), day!(5));
+        assert_eq!(puzzle.to_string(), "2023/05");
+        assert_eq!(puzzle.bin_name(), "2023-05");
+    }
+}